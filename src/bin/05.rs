@@ -3,9 +3,7 @@ use advent_of_code::parser;
 use std::collections::HashSet;
 
 pub fn part_one(_input: &str) -> Option<u64> {
-    let mut split_input = _input.trim().split("\n\n");
-    let page_ordering_rules_section = split_input.next().unwrap().trim();
-    let updates_section = split_input.next().unwrap().trim();
+    let (page_ordering_rules_section, updates_section) = parser::two_sections(_input);
 
     let mut page_ordering_rules = HashSet::new();
     for line in page_ordering_rules_section.lines() {
@@ -32,9 +30,7 @@ pub fn part_one(_input: &str) -> Option<u64> {
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
-    let mut split_input = _input.trim().split("\n\n");
-    let page_ordering_rules_section = split_input.next().unwrap().trim();
-    let updates_section = split_input.next().unwrap().trim();
+    let (page_ordering_rules_section, updates_section) = parser::two_sections(_input);
 
     let mut page_ordering_rules = HashSet::new();
     for line in page_ordering_rules_section.lines() {
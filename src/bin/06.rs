@@ -1,15 +1,17 @@
 advent_of_code::solution!(6);
 
-use std::collections::HashSet;
+use advent_of_code::{BitGrid, BitSet};
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
 
 pub fn part_one(input: &str) -> Option<u64> {
     let map = advent_of_code::parser::to_map(input);
-    let mut directions = [(0, -1), (1, 0), (0, 1), (-1, 0)].iter().cycle();
+    let mut directions = DIRECTIONS.iter().cycle();
 
     let (mut x, mut y) = map.find('^').unwrap();
 
     // Walk the path
-    let mut places = HashSet::new();
+    let mut places = BitGrid::for_map(&map);
     let (mut dx, mut dy) = directions.next().unwrap();
     loop {
         places.insert((x, y));
@@ -35,19 +37,19 @@ pub fn part_two(input: &str) -> Option<u64> {
     for i in 0..map.height {
         for j in 0..map.width {
             let (mut x, mut y) = (x_start, y_start);
-            let mut directions = [(0, -1), (1, 0), (0, 1), (-1, 0)].iter().cycle();
-            let mut places = HashSet::new();
-            let (mut dx, mut dy) = directions.next().unwrap();
+            let mut places = BitSet::new(map.width * map.height * DIRECTIONS.len());
+            let mut direction = 0;
             result += loop {
-                if places.contains(&(x, y, dx, dy)) {
+                let (dx, dy) = DIRECTIONS[direction];
+                let place = (y as usize * map.width + x as usize) * DIRECTIONS.len() + direction;
+                if !places.insert(place) {
                     break 1;
                 }
-                places.insert((x, y, dx, dy));
                 let attempt = (x + dx, y + dy);
                 if !(map.is_in_bounds(attempt.0, attempt.1)) {
                     break 0;
                 } else if map.get(attempt.0, attempt.1) == '#' || attempt == (j as isize, i as isize) {
-                    (dx, dy) = *directions.next().unwrap();
+                    direction = (direction + 1) % DIRECTIONS.len();
                 } else {
                     (x, y) = attempt;
                 }
@@ -1,5 +1,14 @@
 advent_of_code::solution!(7);
 
+use advent_of_code::parser::{self, from_input};
+
+from_input! {
+    struct Equation = "{}: {}" {
+        target: i64,
+        numbers: Vec<i64>,
+    }
+}
+
 fn is_target_reachable(target: i64, numbers: &mut Vec<i64>, operator_pos: usize, allow_concatenate: bool) -> bool {
     if operator_pos == numbers.len() - 1 {
         if numbers[operator_pos] == target {
@@ -36,10 +45,9 @@ fn is_target_reachable(target: i64, numbers: &mut Vec<i64>, operator_pos: usize,
 }
 
 fn solve(input: &str, allow_concatenate: bool) -> Option<u64> {
-    let input = advent_of_code::parser::to_tuples(input, ":", " ");
-    let input = advent_of_code::parser::conversion::to_int_tuples_vec(input);
+    let equations: Vec<Equation> = parser::parse_lines(input).unwrap_or_else(|e| panic!("{e}"));
     let mut count = 0;
-    for (target, mut numbers) in input {
+    for Equation { target, mut numbers } in equations {
         if is_target_reachable(target, &mut numbers, 0, allow_concatenate) {
             count += target;
         }
@@ -3,4 +3,17 @@ mod library;
 
 pub use library::parser;
 pub use library::map::*;
-pub use library::graph::*;
\ No newline at end of file
+pub use library::graph::*;
+pub use library::union_find::*;
+pub use library::assignment::*;
+pub use library::interval_set::*;
+pub use library::math;
+pub use library::linear;
+pub use library::geometry;
+pub use library::sequence;
+pub use library::cycle;
+pub use library::memo::*;
+pub use library::heap::*;
+pub use library::bitset::*;
+pub use library::fenwick::*;
+pub use library::segment_tree::*;
@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignmentError<K, V>
+where
+    K: Eq + Hash,
+{
+    /// No complete assignment exists, `unassigned` keys are left without a value.
+    Unsatisfiable {
+        assigned: HashMap<K, V>,
+        unassigned: Vec<K>,
+    },
+    /// Several assignments exist, `remaining` keys still have several candidates.
+    Ambiguous {
+        assigned: HashMap<K, V>,
+        remaining: HashMap<K, Vec<V>>,
+    },
+}
+
+/// Assigns a distinct value to every key by repeatedly fixing keys with a single candidate
+/// and removing that value from the other keys.
+pub fn assign_unique_candidates<K, V>(
+    candidates: HashMap<K, Vec<V>>,
+) -> Result<HashMap<K, V>, AssignmentError<K, V>>
+where
+    K: Eq + Hash + Clone,
+    V: Eq + Hash + Clone,
+{
+    let mut remaining = candidates;
+    let mut assigned = HashMap::new();
+    while !remaining.is_empty() {
+        if let Some(key) = remaining.iter().find(|(_, values)| values.is_empty()).map(|(key, _)| key.clone()) {
+            remaining.remove(&key);
+            let mut unassigned = vec![key];
+            unassigned.extend(remaining.into_keys());
+            return Err(AssignmentError::Unsatisfiable { assigned, unassigned });
+        }
+
+        let (key, value) = match remaining.iter().find(|(_, values)| values.len() == 1) {
+            Some((key, values)) => (key.clone(), values[0].clone()),
+            None => return Err(AssignmentError::Ambiguous { assigned, remaining }),
+        };
+
+        remaining.remove(&key);
+        remaining.values_mut().for_each(|values| values.retain(|v| *v != value));
+        assigned.insert(key, value);
+    }
+    Ok(assigned)
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_assign_unique_candidates() {
+        let input = "row: class seat\nclass: class\nseat: class row seat";
+        let candidates = crate::parser::to_dict(input, ":", " ");
+        let result = super::assign_unique_candidates(candidates);
+        let expected = std::collections::HashMap::from([("class", "class"), ("row", "seat"), ("seat", "row")]);
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[test]
+    fn test_assign_unique_candidates_partial() {
+        let candidates = std::collections::HashMap::from([(1, vec!['a']), (2, vec!['a', 'b', 'c']), (3, vec!['a', 'b', 'c'])]);
+        let result = super::assign_unique_candidates(candidates);
+        let expected = super::AssignmentError::Ambiguous {
+            assigned: std::collections::HashMap::from([(1, 'a')]),
+            remaining: std::collections::HashMap::from([(2, vec!['b', 'c']), (3, vec!['b', 'c'])]),
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_assign_unique_candidates_errors() {
+        let candidates = std::collections::HashMap::from([(1, vec!['a', 'b']), (2, vec!['a', 'b'])]);
+        let result = super::assign_unique_candidates(candidates.clone());
+        let expected = super::AssignmentError::Ambiguous {
+            assigned: std::collections::HashMap::new(),
+            remaining: candidates,
+        };
+        assert_eq!(result, Err(expected));
+
+        let candidates = std::collections::HashMap::from([(1, vec!['a']), (2, vec!['a'])]);
+        let result = super::assign_unique_candidates(candidates);
+        assert!(matches!(result, Err(super::AssignmentError::Unsatisfiable { .. })));
+    }
+}
@@ -0,0 +1,380 @@
+#![allow(dead_code)]
+
+use super::map::Map;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Fixed-size set of integers in `0..capacity`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(WORD_BITS)],
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns whether the value was not present yet, like `HashSet::insert`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < self.capacity, "{value} is out of the bit set capacity {}", self.capacity);
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        let absent = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        absent
+    }
+
+    /// Returns whether the value was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= self.capacity {
+            return false;
+        }
+        let (word, mask) = (value / WORD_BITS, 1 << (value % WORD_BITS));
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < self.capacity && self.words[value / WORD_BITS] & (1 << (value % WORD_BITS)) != 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Values in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & !b);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.difference_with(other);
+        result
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, &word)| word & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    fn zip_words<F>(&mut self, other: &Self, f: F)
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word = f(*word, other.words.get(i).copied().unwrap_or(0));
+        }
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| {
+            self.insert(value);
+        });
+    }
+}
+
+/// Set of points of a `width` x `height` grid, with the same interface as a `HashSet<(isize, isize)>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    bits: BitSet,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: BitSet::new(width * height),
+            width,
+            height,
+        }
+    }
+
+    /// Empty set with the size of `map`.
+    pub fn for_map(map: &Map) -> Self {
+        Self::new(map.width, map.height)
+    }
+
+    fn index(&self, (x, y): (isize, isize)) -> Option<usize> {
+        let in_bounds = 0 <= x && x < self.width as isize && 0 <= y && y < self.height as isize;
+        in_bounds.then(|| y as usize * self.width + x as usize)
+    }
+
+    /// Panics if the point is out of the grid.
+    pub fn insert(&mut self, point: (isize, isize)) -> bool {
+        let index = self.index(point).unwrap_or_else(|| panic!("{point:?} is out of the grid"));
+        self.bits.insert(index)
+    }
+
+    pub fn remove(&mut self, point: &(isize, isize)) -> bool {
+        self.index(*point).is_some_and(|index| self.bits.remove(index))
+    }
+
+    pub fn contains(&self, point: &(isize, isize)) -> bool {
+        self.index(*point).is_some_and(|index| self.bits.contains(index))
+    }
+
+    pub fn count(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Points in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        self.bits
+            .iter()
+            .map(|index| ((index % self.width) as isize, (index / self.width) as isize))
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.bits.union_with(&other.bits);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.bits.intersect_with(&other.bits);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.bits.difference_with(&other.bits);
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits.is_subset(&other.bits)
+    }
+}
+
+impl Extend<(isize, isize)> for BitGrid {
+    fn extend<I: IntoIterator<Item = (isize, isize)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|point| {
+            self.insert(point);
+        });
+    }
+}
+
+/// Set of integers in `0..128` stored in a single `u128`, meant for bitmask DP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SmallSet(pub u128);
+
+impl SmallSet {
+    pub const EMPTY: Self = Self(0);
+
+    /// Set of `0..n`.
+    pub fn full(n: usize) -> Self {
+        assert!(n <= 128, "a small set holds at most 128 values");
+        Self(if n == 128 { u128::MAX } else { (1 << n) - 1 })
+    }
+
+    /// Panics if `value` is not below 128.
+    pub fn with(self, value: usize) -> Self {
+        assert!(value < 128, "{value} is out of the small set capacity 128");
+        Self(self.0 | 1 << value)
+    }
+
+    pub fn without(self, value: usize) -> Self {
+        if value >= 128 {
+            return self;
+        }
+        Self(self.0 & !(1 << value))
+    }
+
+    pub fn insert(&mut self, value: usize) -> bool {
+        let absent = !self.contains(value);
+        *self = self.with(value);
+        absent
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let present = self.contains(value);
+        *self = self.without(value);
+        present
+    }
+
+    pub fn contains(self, value: usize) -> bool {
+        value < 128 && self.0 & 1 << value != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Values in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let value = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(value)
+        })
+    }
+
+    /// Every subset of the set, from the set itself down to the empty set.
+    pub fn subsets(self) -> impl Iterator<Item = Self> {
+        let mut next = Some(self.0);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = (current != 0).then(|| (current - 1) & self.0);
+            Some(Self(current))
+        })
+    }
+}
+
+impl FromIterator<usize> for SmallSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, Self::with)
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_bit_set() {
+        let mut set = super::BitSet::new(200);
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        set.extend([64, 199, 70]);
+        assert_eq!(set.count(), 4);
+        assert!(set.contains(199));
+        assert!(!set.contains(200));
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![3, 64, 70, 199]);
+
+        let mut other = super::BitSet::new(200);
+        other.extend([3, 70, 100]);
+        assert_eq!(set.union(&other).count(), 5);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<usize>>(), vec![3, 70]);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<usize>>(), vec![64, 199]);
+        assert!(set.intersection(&other).is_subset(&other));
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_bit_grid() {
+        let map = crate::parser::to_map("#..\n.#.\n..#\n...");
+        let mut grid = super::BitGrid::for_map(&map);
+        grid.extend(map.find_all('#'));
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(&(1, 1)));
+        assert!(!grid.contains(&(-1, 1)));
+        assert!(!grid.contains(&(3, 0)));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 2)]);
+        assert!(grid.insert((2, 3)));
+        assert!(grid.remove(&(0, 0)));
+        assert_eq!(grid.count(), 3);
+    }
+
+    #[test]
+    fn test_small_set() {
+        let set = super::SmallSet::from_iter([1, 4, 127]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(127));
+        assert!(!set.contains(2));
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![1, 4, 127]);
+        assert_eq!(super::SmallSet::full(3).difference(set).iter().collect::<Vec<usize>>(), vec![0, 2]);
+        assert_eq!(set.subsets().count(), 8);
+        assert!(set.subsets().all(|subset| subset.is_subset(set)));
+        assert_eq!(super::SmallSet::full(128).len(), 128);
+        let mut visited = super::SmallSet::EMPTY;
+        assert!(visited.insert(5));
+        assert!(!visited.insert(5));
+        assert!(visited.remove(5));
+        assert!(!visited.remove(200));
+        assert!(visited.is_empty());
+    }
+}
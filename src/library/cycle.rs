@@ -0,0 +1,169 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Offset and period of the sequence `initial, step(initial), ...` with Brent's algorithm,
+/// which only keeps two states in memory.
+pub fn brent<T, F>(initial: T, mut step: F) -> (usize, usize)
+where
+    T: PartialEq + Clone,
+    F: FnMut(&T) -> T,
+{
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+    (offset, period)
+}
+
+/// Offset and period of the sequence `initial, step(initial), ...` with Floyd's algorithm.
+pub fn floyd<T, F>(initial: T, mut step: F) -> (usize, usize)
+where
+    T: PartialEq + Clone,
+    F: FnMut(&T) -> T,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let middle = step(&hare);
+        hare = step(&middle);
+    }
+
+    let mut offset = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    (offset, period)
+}
+
+/// States of a sequence up to the first repetition, states at `offset` and
+/// `offset + period` have the same key.
+#[derive(Debug, Clone)]
+pub struct Cycle<T> {
+    pub states: Vec<T>,
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl<T> Cycle<T> {
+    /// Index of a simulated state equivalent to the state after `n` steps.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.offset {
+            n
+        } else {
+            self.offset + (n - self.offset) % self.period
+        }
+    }
+
+    pub fn state_at(&self, n: usize) -> &T {
+        &self.states[self.equivalent_step(n)]
+    }
+
+    /// Extrapolates a quantity that grows by the same amount every period, like the height of a tower.
+    pub fn extrapolate<F>(&self, n: usize, value: F) -> i64
+    where
+        F: Fn(&T) -> i64,
+    {
+        if n < self.offset {
+            return value(&self.states[n]);
+        }
+        let growth = value(&self.states[self.offset + self.period]) - value(&self.states[self.offset]);
+        let cycles = ((n - self.offset) / self.period) as i64;
+        value(self.state_at(n)) + cycles * growth
+    }
+}
+
+/// Simulates from `initial` until a state has the same key as an earlier one.
+/// `key` can drop the parts of the state that do not influence the next steps.
+pub fn find_cycle<T, K, F, G>(initial: T, mut step: F, key: G) -> Cycle<T>
+where
+    K: Eq + Hash,
+    F: FnMut(&T) -> T,
+    G: Fn(&T) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let index = states.len() - 1;
+        if let Some(offset) = seen.insert(key(&states[index]), index) {
+            return Cycle {
+                states,
+                offset,
+                period: index - offset,
+            };
+        }
+        let next = step(&states[index]);
+        states.push(next);
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_brent_floyd() {
+        let step = |x: &u64| (x * x + 1) % 255;
+        let (offset, period) = super::brent(3, step);
+        assert_eq!(super::floyd(3, step), (offset, period));
+        let cycle = super::find_cycle(3, step, |&x| x);
+        assert_eq!((cycle.offset, cycle.period), (offset, period));
+        assert_eq!(super::brent(0, |x: &u64| (x + 1) % 5), (0, 5));
+        assert_eq!(super::floyd(0, |x: &u64| (x + 1) % 5), (0, 5));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // memory reallocation example
+        let step = |banks: &Vec<usize>| {
+            let mut banks = banks.clone();
+            let max = *banks.iter().max().unwrap();
+            let start = banks.iter().position(|&bank| bank == max).unwrap();
+            banks[start] = 0;
+            for i in 1..=max {
+                let len = banks.len();
+                banks[(start + i) % len] += 1;
+            }
+            banks
+        };
+        let cycle = super::find_cycle(vec![0, 2, 7, 0], step, |banks| banks.clone());
+        assert_eq!((cycle.offset, cycle.period), (1, 4));
+        assert_eq!(cycle.states.len(), 6);
+        assert_eq!(cycle.state_at(1_000_000_000_000), &vec![1, 3, 4, 1]);
+        assert_eq!(cycle.state_at(2), &vec![3, 1, 2, 3]);
+
+        let counter = super::find_cycle((0i64, 0u8), |&(total, x)| (total + x as i64, (x + 1) % 3), |&(_, x)| x);
+        assert_eq!((counter.offset, counter.period), (0, 3));
+        assert_eq!(counter.extrapolate(1_000_000_000_000, |&(total, _)| total), 999_999_999_999);
+    }
+}
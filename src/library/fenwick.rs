@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use std::ops::{Add, Bound, RangeBounds, Sub};
+
+/// Binary indexed tree over `len` values, with point updates and range sums in logarithmic time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FenwickTree<T> {
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Tree of `len` values set to `T::default()`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            tree: vec![T::default(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Adds `delta` to the value at `index`.
+    pub fn add(&mut self, index: usize, delta: T) {
+        assert!(index < self.tree.len(), "index {index} is out of the tree of length {}", self.tree.len());
+        let mut i = index + 1;
+        while i <= self.tree.len() {
+            self.tree[i - 1] = self.tree[i - 1] + delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum of the values in `0..end`.
+    pub fn prefix_sum(&self, end: usize) -> T {
+        let mut total = T::default();
+        let mut i = end.min(self.tree.len());
+        while i > 0 {
+            total = total + self.tree[i - 1];
+            i -= i & i.wrapping_neg();
+        }
+        total
+    }
+
+    pub fn range_sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.tree.len(),
+        };
+        if start >= end {
+            return T::default();
+        }
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+
+    pub fn get(&self, index: usize) -> T {
+        self.range_sum(index..=index)
+    }
+
+    /// Replaces the value at `index`, which can only increase it for unsigned types.
+    pub fn set(&mut self, index: usize, value: T) {
+        let delta = value - self.get(index);
+        self.add(index, delta);
+    }
+}
+
+impl<T> From<Vec<T>> for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(values: Vec<T>) -> Self {
+        let mut tree = values;
+        for i in 1..=tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= tree.len() {
+                tree[parent - 1] = tree[parent - 1] + tree[i - 1];
+            }
+        }
+        Self { tree }
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_fenwick_tree() {
+        let values = vec![5i64, -2, 7, 0, 3, 8, -4];
+        let mut tree = super::FenwickTree::from(values.clone());
+        for start in 0..values.len() {
+            for end in start..=values.len() {
+                assert_eq!(tree.range_sum(start..end), values[start..end].iter().sum::<i64>());
+            }
+        }
+        assert_eq!(tree.prefix_sum(3), 10);
+        assert_eq!(tree.range_sum(..), 17);
+        assert_eq!(tree.range_sum(2..=4), 10);
+        tree.add(2, 10);
+        tree.set(6, 1);
+        assert_eq!(tree.get(2), 17);
+        assert_eq!(tree.range_sum(..), 32);
+    }
+
+    #[test]
+    fn test_fenwick_inversions() {
+        let permutation = [3, 1, 4, 0, 2];
+        let mut seen = super::FenwickTree::<u64>::new(permutation.len());
+        let mut inversions = 0;
+        for &value in &permutation {
+            inversions += seen.range_sum(value + 1..);
+            seen.add(value, 1);
+        }
+        assert_eq!(inversions, 6);
+    }
+}
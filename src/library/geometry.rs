@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use super::{map::Direction, math::gcd};
+
+fn edges(vertices: &[(isize, isize)]) -> impl Iterator<Item = ((isize, isize), (isize, isize))> + '_ {
+    vertices.iter().copied().zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the area of the polygon, which is always an integer for lattice vertices.
+pub fn double_area(vertices: &[(isize, isize)]) -> isize {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<isize>()
+        .abs()
+}
+
+/// Area of the polygon with the shoelace formula, vertices may be given in either orientation.
+/// Rounded down when the area is not an integer, see `double_area`.
+pub fn shoelace_area(vertices: &[(isize, isize)]) -> isize {
+    double_area(vertices) / 2
+}
+
+/// Number of lattice points on the boundary of the polygon, which is its length when
+/// every edge is horizontal or vertical.
+pub fn perimeter(vertices: &[(isize, isize)]) -> isize {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd((x2 - x1).abs(), (y2 - y1).abs()))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon with Pick's theorem.
+pub fn interior_lattice_points(vertices: &[(isize, isize)]) -> isize {
+    (double_area(vertices) - perimeter(vertices) + 2) / 2
+}
+
+/// Vertices visited by following the instructions from `start`, without repeating `start`
+/// at the end of a closed path.
+pub fn polygon_from_instructions(
+    start: (isize, isize),
+    instructions: impl IntoIterator<Item = (Direction, isize)>,
+) -> Vec<(isize, isize)> {
+    let mut vertices = vec![start];
+    let (mut x, mut y) = start;
+    for (direction, length) in instructions {
+        let (dx, dy) = direction.move_forward(0, 0);
+        (x, y) = (x + dx * length, y + dy * length);
+        vertices.push((x, y));
+    }
+    if vertices.len() > 1 && vertices.last() == Some(&start) {
+        vertices.pop();
+    }
+    vertices
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_polygon_measures() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(super::shoelace_area(&square), 16);
+        assert_eq!(super::perimeter(&square), 16);
+        assert_eq!(super::interior_lattice_points(&square), 9);
+
+        let triangle = [(0, 0), (0, 3), (3, 0)];
+        assert_eq!(super::double_area(&triangle), 9);
+        assert_eq!(super::shoelace_area(&triangle), 4);
+        assert_eq!(super::perimeter(&triangle), 9);
+        assert_eq!(super::interior_lattice_points(&triangle), 1);
+    }
+
+    #[test]
+    fn test_polygon_from_instructions() {
+        use crate::Direction::*;
+        let input = "R 6\nD 5\nL 2\nD 2\nR 2\nD 2\nL 5\nU 2\nL 1\nU 2\nR 2\nU 3\nL 2\nU 2";
+        let instructions = input.lines().map(|line| {
+            let (direction, length) = line.split_once(' ').unwrap();
+            let direction = match direction {
+                "U" => Up,
+                "R" => Right,
+                "D" => Down,
+                _ => Left,
+            };
+            (direction, length.parse().unwrap())
+        });
+        let vertices = super::polygon_from_instructions((0, 0), instructions);
+        assert_eq!(vertices.len(), 14);
+        assert_eq!(vertices[..3], [(0, 0), (6, 0), (6, 5)]);
+        let lagoon = super::interior_lattice_points(&vertices) + super::perimeter(&vertices);
+        assert_eq!(lagoon, 62);
+    }
+}
@@ -1,7 +1,16 @@
 #![allow(dead_code)]
 
-use super::lib::binary_heap_node::BinaryHeapNode;
-use std::{collections::HashMap, fmt, hash::Hash};
+use super::assignment::AssignmentError;
+use super::heap::MinHeap;
+use super::map::{Direction, Map};
+use super::parser::{self, ParseError};
+use super::union_find::UnionFind;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+    num::ParseIntError,
+};
 
 #[derive(Debug, Copy, Clone)]
 struct Edge<N> {
@@ -36,13 +45,13 @@ where
     }
 
     pub fn add_node(&mut self, node: N) {
-        self.adjecency_list.entry(node).or_insert(Vec::new());
+        self.adjecency_list.entry(node).or_default();
     }
 
     pub fn add_edge_directed(&mut self, node1: N, node2: N) {
         self.adjecency_list
             .entry(node1)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node1, node2, 1));
         self.add_node(node2);
     }
@@ -50,7 +59,7 @@ where
     pub fn add_edge_directed_weighted(&mut self, node1: N, node2: N, weight: i32) {
         self.adjecency_list
             .entry(node1)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node1, node2, weight));
         self.add_node(node2);
     }
@@ -58,22 +67,22 @@ where
     pub fn add_edge_undirected(&mut self, node1: N, node2: N) {
         self.adjecency_list
             .entry(node1)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node1, node2, 1));
         self.adjecency_list
             .entry(node2)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node2, node1, 1));
     }
 
     pub fn add_edge_undirected_weighted(&mut self, node1: N, node2: N, weight: i32) {
         self.adjecency_list
             .entry(node1)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node1, node2, weight));
         self.adjecency_list
             .entry(node2)
-            .or_insert(Vec::new())
+            .or_default()
             .push(Edge::new(node2, node1, weight));
     }
 
@@ -90,9 +99,8 @@ where
     where
         F: FnMut(N),
     {
-        match self.get_neighbors(node) {
-            Some(neighbors) => neighbors.iter().for_each(|&neighbor| f(neighbor)),
-            None => (),
+        if let Some(neighbors) = self.get_neighbors(node) {
+            neighbors.iter().for_each(|&neighbor| f(neighbor));
         }
     }
 
@@ -100,9 +108,8 @@ where
     where
         F: FnMut(Edge<N>),
     {
-        match self.adjecency_list.get(&node) {
-            Some(edges) => edges.iter().for_each(|&edge| f(edge)),
-            None => (),
+        if let Some(edges) = self.adjecency_list.get(&node) {
+            edges.iter().for_each(|&edge| f(edge));
         }
     }
 
@@ -110,35 +117,571 @@ where
     where
         F: Fn(N) -> bool,
     {
-        let mut heap = std::collections::BinaryHeap::new();
+        let mut heap = MinHeap::new();
         let mut parents = HashMap::new();
-        heap.push(BinaryHeapNode::new(Edge::new(start, start, 0), 0));
-        while let Some(BinaryHeapNode {
-            value: edge,
-            priority,
-        }) = heap.pop()
-        {
-            if parents.contains_key(&edge.destination) {
-                continue;
-            }
-            parents.insert(edge.destination, edge.start);
-            if stop_condition(edge.destination) {
-                let mut path = Vec::from([edge.destination]);
-                let mut current = edge.destination;
+        let mut candidates = HashMap::from([(start, start)]);
+        heap.push(start, 0);
+        while let Some((node, cost)) = heap.pop() {
+            parents.insert(node, candidates[&node]);
+            if stop_condition(node) {
+                let mut path = Vec::from([node]);
+                let mut current = node;
                 while current != start {
                     current = parents[&current];
                     path.push(current);
                 }
-                return Some((path, -priority as isize));
+                return Some((path, cost));
             }
-            self.iter_edges(edge.destination, |edge| {
-                if !parents.contains_key(&edge.destination) {
-                    heap.push(BinaryHeapNode::new(edge, priority - edge.weight));
+            self.iter_edges(node, |edge| {
+                if !parents.contains_key(&edge.destination) && heap.push(edge.destination, cost + edge.weight as isize) {
+                    candidates.insert(edge.destination, node);
                 }
             });
         }
         None
     }
+
+    /// Kruskal's algorithm, returns the edges of a minimum spanning forest and its total weight.
+    pub fn minimum_spanning_tree(&self) -> (Vec<(N, N, i32)>, isize) {
+        let index = self
+            .adjecency_list
+            .keys()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect::<HashMap<N, usize>>();
+        let mut edges = self
+            .adjecency_list
+            .values()
+            .flatten()
+            .copied()
+            .collect::<Vec<Edge<N>>>();
+        edges.sort_by_key(|edge| edge.weight);
+
+        let mut union_find = UnionFind::new(index.len());
+        let mut tree = Vec::new();
+        let mut total = 0;
+        for edge in edges {
+            if union_find.union(index[&edge.start], index[&edge.destination]) {
+                tree.push((edge.start, edge.destination, edge.weight));
+                total += edge.weight as isize;
+            }
+        }
+        (tree, total)
+    }
+
+    /// Prim's algorithm, returns the edges of a minimum spanning tree of the component of `start`.
+    pub fn minimum_spanning_tree_prim(&self, start: N) -> (Vec<(N, N, i32)>, isize) {
+        let mut heap = MinHeap::new();
+        let mut candidates = HashMap::new();
+        let mut visited = HashSet::from([start]);
+        let mut tree = Vec::new();
+        let mut total = 0;
+        self.iter_edges(start, |edge| {
+            if edge.destination != start && heap.push(edge.destination, edge.weight) {
+                candidates.insert(edge.destination, edge.start);
+            }
+        });
+        while let Some((node, weight)) = heap.pop() {
+            visited.insert(node);
+            tree.push((candidates[&node], node, weight));
+            total += weight as isize;
+            self.iter_edges(node, |edge| {
+                if !visited.contains(&edge.destination) && heap.push(edge.destination, edge.weight) {
+                    candidates.insert(edge.destination, edge.start);
+                }
+            });
+        }
+        (tree, total)
+    }
+
+    /// Hopcroft-Karp maximum matching between `left` nodes and their neighbors.
+    /// Fails with the partial matching if some `left` node cannot be matched, and with the
+    /// candidates of every `left` node that can be matched in several ways if the matching is not unique.
+    pub fn bipartite_matching(&self, left: &[N]) -> Result<HashMap<N, N>, AssignmentError<N, N>> {
+        let mut right = Vec::new();
+        let mut right_index = HashMap::new();
+        let adjacency = left
+            .iter()
+            .map(|&node| {
+                self.get_neighbors(node)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|neighbor| {
+                        *right_index.entry(neighbor).or_insert_with(|| {
+                            right.push(neighbor);
+                            right.len() - 1
+                        })
+                    })
+                    .collect::<Vec<usize>>()
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut pair_left = vec![UNMATCHED; left.len()];
+        let mut pair_right = vec![UNMATCHED; right.len()];
+        let mut distance = vec![0; left.len()];
+        while matching_layers(&adjacency, &pair_left, &pair_right, &mut distance) {
+            for u in 0..left.len() {
+                if pair_left[u] == UNMATCHED {
+                    augment_matching(u, &adjacency, &mut pair_left, &mut pair_right, &mut distance);
+                }
+            }
+        }
+
+        let mut assigned = (0..left.len())
+            .filter(|&u| pair_left[u] != UNMATCHED)
+            .map(|u| (left[u], right[pair_left[u]]))
+            .collect::<HashMap<N, N>>();
+        let unassigned = (0..left.len())
+            .filter(|&u| pair_left[u] == UNMATCHED)
+            .map(|u| left[u])
+            .collect::<Vec<N>>();
+        if !unassigned.is_empty() {
+            return Err(AssignmentError::Unsatisfiable { assigned, unassigned });
+        }
+
+        let mut remaining = HashMap::new();
+        for u in 0..left.len() {
+            let candidates = adjacency[u]
+                .iter()
+                .filter(|&&v| can_match(u, v, &adjacency, &pair_left, &pair_right))
+                .map(|&v| right[v])
+                .collect::<Vec<N>>();
+            if candidates.len() > 1 {
+                assigned.remove(&left[u]);
+                remaining.insert(left[u], candidates);
+            }
+        }
+        if remaining.is_empty() {
+            Ok(assigned)
+        } else {
+            Err(AssignmentError::Ambiguous { assigned, remaining })
+        }
+    }
+
+    /// Hierholzer's algorithm, returns the nodes of a path using every edge exactly once.
+    pub fn eulerian_path(&self, directed: bool) -> Option<Vec<N>> {
+        let (nodes, edges) = if directed {
+            self.directed_edges()
+        } else {
+            self.undirected_edges()
+        };
+        if edges.is_empty() {
+            return None;
+        }
+        let mut adjacency = vec![Vec::new(); nodes.len()];
+        let mut balance = vec![0isize; nodes.len()];
+        for (id, &(u, v)) in edges.iter().enumerate() {
+            adjacency[u].push((v, id));
+            balance[u] += 1;
+            if directed {
+                balance[v] -= 1;
+            } else {
+                adjacency[v].push((u, id));
+                balance[v] += 1;
+            }
+        }
+        let start = if directed {
+            let starts = (0..nodes.len()).filter(|&u| balance[u] == 1).collect::<Vec<usize>>();
+            let ends = (0..nodes.len()).filter(|&u| balance[u] == -1).count();
+            let unbalanced = balance.iter().filter(|&&b| b != 0).count();
+            match (starts.len(), ends, unbalanced) {
+                (0, 0, 0) => edges[0].0,
+                (1, 1, 2) => starts[0],
+                _ => return None,
+            }
+        } else {
+            let odd = (0..nodes.len()).filter(|&u| balance[u] % 2 == 1).collect::<Vec<usize>>();
+            match odd.len() {
+                0 => edges[0].0,
+                2 => odd[0],
+                _ => return None,
+            }
+        };
+
+        let mut used = vec![false; edges.len()];
+        let mut next = vec![0; nodes.len()];
+        let mut stack = vec![start];
+        let mut path = Vec::new();
+        while let Some(&u) = stack.last() {
+            while next[u] < adjacency[u].len() && used[adjacency[u][next[u]].1] {
+                next[u] += 1;
+            }
+            if next[u] < adjacency[u].len() {
+                let (v, id) = adjacency[u][next[u]];
+                used[id] = true;
+                stack.push(v);
+            } else {
+                path.push(nodes[u]);
+                stack.pop();
+            }
+        }
+        if path.len() != edges.len() + 1 {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn eulerian_circuit(&self, directed: bool) -> Option<Vec<N>> {
+        self.eulerian_path(directed).filter(|path| path.first() == path.last())
+    }
+
+    /// Tarjan's bridges of an undirected graph.
+    pub fn bridges(&self) -> Vec<(N, N)> {
+        let (nodes, edges) = self.undirected_edges();
+        let (bridges, _) = tarjan_low_links(nodes.len(), &edges);
+        bridges
+            .into_iter()
+            .map(|id| (nodes[edges[id].0], nodes[edges[id].1]))
+            .collect()
+    }
+
+    /// Tarjan's articulation points of an undirected graph.
+    pub fn articulation_points(&self) -> Vec<N> {
+        let (nodes, edges) = self.undirected_edges();
+        let (_, articulation_points) = tarjan_low_links(nodes.len(), &edges);
+        articulation_points.into_iter().map(|u| nodes[u]).collect()
+    }
+
+    /// Kahn's algorithm, returns `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<N>> {
+        let mut in_degree = self
+            .adjecency_list
+            .keys()
+            .map(|&node| (node, 0))
+            .collect::<HashMap<N, usize>>();
+        self.adjecency_list
+            .values()
+            .flatten()
+            .for_each(|edge| *in_degree.get_mut(&edge.destination).unwrap() += 1);
+        let mut queue = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&node, _)| node)
+            .collect::<VecDeque<N>>();
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            self.iter_neighbors(node, |neighbor| {
+                let degree = in_degree.get_mut(&neighbor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(neighbor);
+                }
+            });
+        }
+        if order.len() == self.adjecency_list.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_dag(&self) -> bool {
+        self.topological_sort().is_some()
+    }
+
+    /// Whether an undirected graph is connected and acyclic.
+    pub fn is_tree(&self) -> bool {
+        let (nodes, edges) = self.undirected_edges();
+        if nodes.is_empty() || edges.len() != nodes.len() - 1 {
+            return false;
+        }
+        let mut union_find = UnionFind::new(nodes.len());
+        edges.iter().for_each(|&(u, v)| {
+            union_find.union(u, v);
+        });
+        union_find.components() == 1
+    }
+
+    fn directed_edges(&self) -> (Vec<N>, Vec<(usize, usize)>) {
+        let nodes = self.adjecency_list.keys().copied().collect::<Vec<N>>();
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect::<HashMap<N, usize>>();
+        let edges = nodes
+            .iter()
+            .flat_map(|node| self.adjecency_list[node].iter())
+            .map(|edge| (index[&edge.start], index[&edge.destination]))
+            .collect();
+        (nodes, edges)
+    }
+
+    fn undirected_edges(&self) -> (Vec<N>, Vec<(usize, usize)>) {
+        let (nodes, edges) = self.directed_edges();
+        (nodes, single_undirected_copies(edges, |&edge| edge))
+    }
+}
+
+// Undirected edges are stored once per direction, keep a single copy of each.
+// Both copies of a self-loop have the same ends, so every other one is kept.
+fn single_undirected_copies<E, F>(edges: Vec<E>, ends: F) -> Vec<E>
+where
+    F: Fn(&E) -> (usize, usize),
+{
+    let mut self_loop = false;
+    edges
+        .into_iter()
+        .filter(|edge| {
+            let (u, v) = ends(edge);
+            if u == v {
+                self_loop = !self_loop;
+                self_loop
+            } else {
+                u < v
+            }
+        })
+        .collect()
+}
+
+const UNMATCHED: usize = usize::MAX;
+const UNVISITED: usize = usize::MAX;
+
+// Returns the bridge edge ids and the articulation nodes of an undirected graph.
+fn tarjan_low_links(size: usize, edges: &[(usize, usize)]) -> (Vec<usize>, Vec<usize>) {
+    let mut adjacency = vec![Vec::new(); size];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adjacency[u].push((v, id));
+        adjacency[v].push((u, id));
+    }
+    let mut discovery = vec![UNVISITED; size];
+    let mut low = vec![0; size];
+    let mut is_articulation = vec![false; size];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+    for root in 0..size {
+        if discovery[root] != UNVISITED {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        let mut root_children = 0;
+        // (node, edge used to reach it, next neighbor to explore)
+        let mut stack = vec![(root, usize::MAX, 0)];
+        while let Some(&(u, parent_edge, i)) = stack.last() {
+            if i < adjacency[u].len() {
+                stack.last_mut().unwrap().2 += 1;
+                let (v, id) = adjacency[u][i];
+                if id == parent_edge {
+                    continue;
+                }
+                if discovery[v] == UNVISITED {
+                    discovery[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    if u == root {
+                        root_children += 1;
+                    }
+                    stack.push((v, id, 0));
+                } else {
+                    low[u] = low[u].min(discovery[v]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(parent, _, _)) = stack.last() {
+                    low[parent] = low[parent].min(low[u]);
+                    if low[u] > discovery[parent] {
+                        bridges.push(parent_edge);
+                    }
+                    if parent != root && low[u] >= discovery[parent] {
+                        is_articulation[parent] = true;
+                    }
+                }
+            }
+        }
+        if root_children > 1 {
+            is_articulation[root] = true;
+        }
+    }
+    let articulation_points = (0..size).filter(|&u| is_articulation[u]).collect();
+    (bridges, articulation_points)
+}
+
+fn matching_layers(adjacency: &[Vec<usize>], pair_left: &[usize], pair_right: &[usize], distance: &mut [usize]) -> bool {
+    let mut queue = VecDeque::new();
+    for u in 0..adjacency.len() {
+        if pair_left[u] == UNMATCHED {
+            distance[u] = 0;
+            queue.push_back(u);
+        } else {
+            distance[u] = usize::MAX;
+        }
+    }
+    let mut found = false;
+    while let Some(u) = queue.pop_front() {
+        for &v in &adjacency[u] {
+            match pair_right[v] {
+                UNMATCHED => found = true,
+                w if distance[w] == usize::MAX => {
+                    distance[w] = distance[u] + 1;
+                    queue.push_back(w);
+                }
+                _ => (),
+            }
+        }
+    }
+    found
+}
+
+fn augment_matching(
+    u: usize,
+    adjacency: &[Vec<usize>],
+    pair_left: &mut [usize],
+    pair_right: &mut [usize],
+    distance: &mut [usize],
+) -> bool {
+    for &v in &adjacency[u] {
+        let w = pair_right[v];
+        if w == UNMATCHED
+            || (distance[w] == distance[u] + 1 && augment_matching(w, adjacency, pair_left, pair_right, distance))
+        {
+            pair_left[u] = v;
+            pair_right[v] = u;
+            return true;
+        }
+    }
+    distance[u] = usize::MAX;
+    false
+}
+
+// Whether some other complete matching pairs `u` with `v`, by forcing that pair and rematching
+// the left node previously matched with `v`.
+fn can_match(u: usize, v: usize, adjacency: &[Vec<usize>], pair_left: &[usize], pair_right: &[usize]) -> bool {
+    if pair_left[u] == v {
+        return true;
+    }
+    let mut pair_left = pair_left.to_vec();
+    let mut pair_right = pair_right.to_vec();
+    let w = pair_right[v];
+    pair_right[pair_left[u]] = UNMATCHED;
+    pair_left[u] = v;
+    pair_right[v] = u;
+    if w == UNMATCHED {
+        return true;
+    }
+    pair_left[w] = UNMATCHED;
+    let mut visited = vec![false; pair_right.len()];
+    visited[v] = true;
+    rematch(w, adjacency, &mut pair_left, &mut pair_right, &mut visited)
+}
+
+// Kuhn's augmenting path search from the unmatched left node `u`.
+fn rematch(
+    u: usize,
+    adjacency: &[Vec<usize>],
+    pair_left: &mut [usize],
+    pair_right: &mut [usize],
+    visited: &mut [bool],
+) -> bool {
+    for &v in &adjacency[u] {
+        if visited[v] {
+            continue;
+        }
+        visited[v] = true;
+        let w = pair_right[v];
+        if w == UNMATCHED || rematch(w, adjacency, pair_left, pair_right, visited) {
+            pair_left[u] = v;
+            pair_right[v] = u;
+            return true;
+        }
+    }
+    false
+}
+
+impl<'a> Graph<&'a str> {
+    /// Builds a graph from lines like `key: a b c`.
+    pub fn try_from_adjacency_lines(
+        input: &'a str,
+        key_separator: &'a str,
+        value_separator: &'a str,
+        directed: bool,
+    ) -> Result<Self, ParseError> {
+        let mut graph = Self::new();
+        for (key, values) in parser::try_to_tuples(input, key_separator, value_separator)? {
+            graph.add_node(key);
+            for value in values.into_iter().map(str::trim).filter(|value| !value.is_empty()) {
+                if directed {
+                    graph.add_edge_directed(key, value);
+                } else {
+                    graph.add_edge_undirected(key, value);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn from_adjacency_lines(input: &'a str, key_separator: &'a str, value_separator: &'a str, directed: bool) -> Self {
+        Self::try_from_adjacency_lines(input, key_separator, value_separator, directed)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds a graph from lines like `a-b` or `a,b,3`, the optional third column is the weight.
+    pub fn try_from_edge_list(input: &'a str, separator: &'a str, directed: bool) -> Result<Self, ParseError> {
+        let mut graph = Self::new();
+        for line in input.trim().lines() {
+            let mut columns = line.split(separator);
+            let start = columns.next().unwrap_or_default().trim();
+            let destination = columns
+                .next()
+                .ok_or_else(|| ParseError::new(input, line, format!("missing separator {:?}", separator)))?
+                .trim();
+            let weight = match columns.next() {
+                Some(weight) => weight
+                    .trim()
+                    .parse()
+                    .map_err(|error: ParseIntError| ParseError::new(input, weight, error.to_string()))?,
+                None => 1,
+            };
+            if directed {
+                graph.add_edge_directed_weighted(start, destination, weight);
+            } else {
+                graph.add_edge_undirected_weighted(start, destination, weight);
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn from_edge_list(input: &'a str, separator: &'a str, directed: bool) -> Self {
+        Self::try_from_edge_list(input, separator, directed).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Graph<(isize, isize)> {
+    /// Builds a graph linking each passable cell to its passable neighbors, weighted by the cost of entering them.
+    pub fn from_map<P, C>(map: &Map, passable: P, cost: C) -> Self
+    where
+        P: Fn(char) -> bool,
+        C: Fn(char) -> i32,
+    {
+        let mut graph = Self::new();
+        for y in 0..map.height as isize {
+            for x in 0..map.width as isize {
+                if !passable(map.get(x, y)) {
+                    continue;
+                }
+                graph.add_node((x, y));
+                for direction in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+                    let (nx, ny) = direction.move_forward(x, y);
+                    if map.is_in_bounds(nx, ny) && passable(map.get(nx, ny)) {
+                        graph.add_edge_directed_weighted((x, y), (nx, ny), cost(map.get(nx, ny)));
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl<N> Default for Graph<N>
+where
+    N: Eq + Hash + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<N> fmt::Display for Graph<N>
@@ -166,6 +709,140 @@ where
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ExportOptions<N> {
+    pub directed: bool,
+    pub weights: bool,
+    pub labels: HashMap<N, String>,
+    pub highlighted_nodes: HashSet<N>,
+    pub highlighted_edges: HashSet<(N, N)>,
+}
+
+impl<N> Default for ExportOptions<N> {
+    fn default() -> Self {
+        Self {
+            directed: true,
+            weights: false,
+            labels: HashMap::new(),
+            highlighted_nodes: HashSet::new(),
+            highlighted_edges: HashSet::new(),
+        }
+    }
+}
+
+impl<N> ExportOptions<N>
+where
+    N: Eq + Hash + Copy + fmt::Display,
+{
+    fn label(&self, node: N) -> String {
+        self.labels.get(&node).cloned().unwrap_or_else(|| node.to_string())
+    }
+
+    fn is_highlighted(&self, start: N, destination: N) -> bool {
+        self.highlighted_edges.contains(&(start, destination))
+            || (!self.directed && self.highlighted_edges.contains(&(destination, start)))
+    }
+}
+
+impl<N> Graph<N>
+where
+    N: Eq + Hash + Copy + Ord + fmt::Display,
+{
+    /// Graphviz representation, nodes and edges are sorted so the output can be diffed.
+    pub fn to_dot(&self, options: &ExportOptions<N>) -> String {
+        let (nodes, edges) = self.sorted_edges(options.directed);
+        let mut lines = vec![if options.directed { "digraph {" } else { "graph {" }.to_string()];
+        for (i, &node) in nodes.iter().enumerate() {
+            let highlight = if options.highlighted_nodes.contains(&node) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
+            let label = options.label(node).replace('\\', "\\\\").replace('"', "\\\"");
+            lines.push(format!("    n{} [label=\"{}\"{}];", i, label, highlight));
+        }
+        let arrow = if options.directed { "->" } else { "--" };
+        for &(start, destination, weight) in edges.iter() {
+            let mut attributes = Vec::new();
+            if options.weights {
+                attributes.push(format!("label=\"{}\"", weight));
+            }
+            if options.is_highlighted(nodes[start], nodes[destination]) {
+                attributes.push("color=red, penwidth=2".to_string());
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            lines.push(format!("    n{} {} n{}{};", start, arrow, destination, attributes));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Mermaid flowchart representation, nodes and edges are sorted so the output can be diffed.
+    pub fn to_mermaid(&self, options: &ExportOptions<N>) -> String {
+        let (nodes, edges) = self.sorted_edges(options.directed);
+        let mut lines = vec!["flowchart LR".to_string()];
+        for (i, &node) in nodes.iter().enumerate() {
+            // Mermaid does not support backslash escapes, quotes use an entity code
+            let label = options.label(node).replace('"', "#quot;");
+            lines.push(format!("    n{}[\"{}\"]", i, label));
+        }
+        let arrow = if options.directed { "-->" } else { "---" };
+        for &(start, destination, weight) in edges.iter() {
+            let weight = if options.weights {
+                format!("|{}|", weight)
+            } else {
+                String::new()
+            };
+            lines.push(format!("    n{} {}{} n{}", start, arrow, weight, destination));
+        }
+        let highlighted_nodes = (0..nodes.len())
+            .filter(|&i| options.highlighted_nodes.contains(&nodes[i]))
+            .map(|i| format!("n{}", i))
+            .collect::<Vec<String>>();
+        if !highlighted_nodes.is_empty() {
+            lines.push("    classDef highlight stroke:#f00,stroke-width:3px;".to_string());
+            lines.push(format!("    class {} highlight;", highlighted_nodes.join(",")));
+        }
+        let highlighted_edges = edges
+            .iter()
+            .enumerate()
+            .filter(|(_, &(start, destination, _))| options.is_highlighted(nodes[start], nodes[destination]))
+            .map(|(i, _)| i.to_string())
+            .collect::<Vec<String>>();
+        if !highlighted_edges.is_empty() {
+            lines.push(format!("    linkStyle {} stroke:#f00,stroke-width:3px;", highlighted_edges.join(",")));
+        }
+        lines.join("\n")
+    }
+
+    fn sorted_edges(&self, directed: bool) -> (Vec<N>, Vec<(usize, usize, i32)>) {
+        let mut nodes = self.adjecency_list.keys().copied().collect::<Vec<N>>();
+        nodes.sort();
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect::<HashMap<N, usize>>();
+        let mut edges = Vec::new();
+        for node in nodes.iter() {
+            let mut node_edges = self.adjecency_list[node]
+                .iter()
+                .map(|edge| (index[&edge.start], index[&edge.destination], edge.weight))
+                .collect::<Vec<(usize, usize, i32)>>();
+            node_edges.sort();
+            edges.extend(node_edges);
+        }
+        if !directed {
+            edges = single_undirected_copies(edges, |&(start, destination, _)| (start, destination));
+        }
+        (nodes, edges)
+    }
+}
+
 #[cfg(feature = "test_aoc_lib")]
 mod tests {
 
@@ -200,4 +877,250 @@ mod tests {
         let result = graph.dijsktra(1, |node| node == 7);
         assert_eq!(result, Some((vec![7, 6, 5, 4, 3, 1], 12)));
     }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let mut graph = super::Graph::new();
+        let edges = [(1, 2, 7), (1, 4, 5), (2, 3, 8), (2, 4, 9), (2, 5, 7), (3, 5, 5), (4, 5, 15), (4, 6, 6), (5, 6, 8), (5, 7, 9), (6, 7, 11)];
+        edges.iter().for_each(|&(start, destination, weight)| {
+            graph.add_edge_undirected_weighted(start, destination, weight);
+        });
+        let (kruskal, kruskal_weight) = graph.minimum_spanning_tree();
+        assert_eq!(kruskal.len(), 6);
+        assert_eq!(kruskal_weight, 39);
+        let (prim, prim_weight) = graph.minimum_spanning_tree_prim(1);
+        assert_eq!(prim.len(), 6);
+        assert_eq!(prim_weight, 39);
+    }
+
+    #[test]
+    fn test_minimum_spanning_forest() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_undirected_weighted(1, 2, 3);
+        graph.add_edge_undirected_weighted(3, 4, 1);
+        graph.add_node(5);
+        let (forest, weight) = graph.minimum_spanning_tree();
+        assert_eq!(forest.len(), 2);
+        assert_eq!(weight, 4);
+        let (tree, weight) = graph.minimum_spanning_tree_prim(3);
+        assert_eq!(tree, vec![(3, 4, 1)]);
+        assert_eq!(weight, 1);
+    }
+
+    #[test]
+    fn test_bipartite_matching() {
+        let input = "1: a b\n2: a c\n3: c";
+        let mut graph = super::Graph::new();
+        crate::parser::to_dict(input, ":", " ").into_iter().for_each(|(key, values)| {
+            values.into_iter().for_each(|value| graph.add_edge_directed(value, key));
+        });
+        let result = graph.bipartite_matching(&["a", "b", "c"]);
+        let expected = std::collections::HashMap::from([("a", "2"), ("b", "1"), ("c", "3")]);
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[test]
+    fn test_bipartite_matching_ambiguous() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_directed("a", "x");
+        graph.add_edge_directed("a", "y");
+        graph.add_edge_directed("b", "x");
+        graph.add_edge_directed("b", "y");
+        graph.add_edge_directed("c", "z");
+        let result = graph.bipartite_matching(&["a", "b", "c"]);
+        match result {
+            Err(super::AssignmentError::Ambiguous { assigned, mut remaining }) => {
+                assert_eq!(assigned, std::collections::HashMap::from([("c", "z")]));
+                remaining.values_mut().for_each(|candidates| candidates.sort());
+                let expected = std::collections::HashMap::from([("a", vec!["x", "y"]), ("b", vec!["x", "y"])]);
+                assert_eq!(remaining, expected);
+            }
+            _ => panic!("Expected an ambiguous matching"),
+        }
+
+        // Another matching only exists through a free right node
+        graph.add_edge_directed("d", "w");
+        graph.add_edge_directed("d", "v");
+        let result = graph.bipartite_matching(&["c", "d"]);
+        assert!(matches!(result, Err(super::AssignmentError::Ambiguous { .. })));
+    }
+
+    #[test]
+    fn test_bipartite_matching_unsatisfiable() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_directed('a', 'x');
+        graph.add_edge_directed('b', 'x');
+        graph.add_edge_directed('c', 'y');
+        let result = graph.bipartite_matching(&['a', 'b', 'c']);
+        match result {
+            Err(super::AssignmentError::Unsatisfiable { assigned, unassigned }) => {
+                assert_eq!(assigned.len(), 2);
+                assert_eq!(unassigned.len(), 1);
+            }
+            _ => panic!("Expected an unsatisfiable matching"),
+        }
+    }
+
+    #[test]
+    fn test_eulerian_path() {
+        let mut graph = super::Graph::new();
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4)];
+        edges.iter().for_each(|&(start, destination)| {
+            graph.add_edge_directed(start, destination);
+        });
+        assert_eq!(graph.eulerian_path(true), Some(vec![3, 1, 2, 3, 4]));
+        assert_eq!(graph.eulerian_circuit(true), None);
+
+        let mut graph = super::Graph::new();
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)];
+        edges.iter().for_each(|&(start, destination)| {
+            graph.add_edge_undirected(start, destination);
+        });
+        let circuit = graph.eulerian_circuit(false).unwrap();
+        assert_eq!(circuit.len(), 7);
+        assert!(circuit.windows(2).all(|pair| graph.get_neighbors(pair[0]).unwrap().contains(&pair[1])));
+        graph.add_edge_undirected(1, 4);
+        let path = graph.eulerian_path(false).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(graph.eulerian_circuit(false), None);
+    }
+
+    #[test]
+    fn test_bridges_and_articulation_points() {
+        let mut graph = super::Graph::new();
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (6, 4), (6, 7)];
+        edges.iter().for_each(|&(start, destination)| {
+            graph.add_edge_undirected(start, destination);
+        });
+        let mut bridges = graph
+            .bridges()
+            .into_iter()
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<(i32, i32)>>();
+        bridges.sort();
+        assert_eq!(bridges, vec![(3, 4), (6, 7)]);
+        let mut articulation_points = graph.articulation_points();
+        articulation_points.sort();
+        assert_eq!(articulation_points, vec![3, 4, 6]);
+    }
+
+    #[test]
+    fn test_is_tree_and_is_dag() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_undirected(1, 2);
+        graph.add_edge_undirected(1, 3);
+        graph.add_edge_undirected(3, 4);
+        assert!(graph.is_tree());
+        graph.add_edge_undirected(2, 4);
+        assert!(!graph.is_tree());
+
+        let mut graph = super::Graph::new();
+        graph.add_edge_directed(1, 2);
+        graph.add_edge_directed(1, 3);
+        graph.add_edge_directed(3, 2);
+        assert!(graph.is_dag());
+        assert_eq!(graph.topological_sort(), Some(vec![1, 3, 2]));
+        graph.add_edge_directed(2, 1);
+        assert!(!graph.is_dag());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_directed_weighted(2, 3, 4);
+        graph.add_edge_directed_weighted(1, 2, 5);
+        let mut options = super::ExportOptions {
+            weights: true,
+            ..Default::default()
+        };
+        options.labels.insert(1, "start".to_string());
+        options.highlighted_nodes.insert(3);
+        options.highlighted_edges.insert((1, 2));
+        let expected = [
+            "digraph {",
+            "    n0 [label=\"start\"];",
+            "    n1 [label=\"2\"];",
+            "    n2 [label=\"3\", color=red, penwidth=2];",
+            "    n0 -> n1 [label=\"5\", color=red, penwidth=2];",
+            "    n1 -> n2 [label=\"4\"];",
+            "}",
+        ];
+        assert_eq!(graph.to_dot(&options), expected.join("\n"));
+
+        options.labels.insert(3, "end\\".to_string());
+        assert!(graph.to_dot(&options).contains("n2 [label=\"end\\\\\", color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mut graph = super::Graph::new();
+        graph.add_edge_undirected("b", "a");
+        graph.add_edge_undirected("b", "c");
+        let mut options = super::ExportOptions {
+            directed: false,
+            ..Default::default()
+        };
+        options.highlighted_nodes.insert("a");
+        options.highlighted_edges.insert(("c", "b"));
+        let expected = [
+            "flowchart LR",
+            "    n0[\"a\"]",
+            "    n1[\"b\"]",
+            "    n2[\"c\"]",
+            "    n0 --- n1",
+            "    n1 --- n2",
+            "    classDef highlight stroke:#f00,stroke-width:3px;",
+            "    class n0 highlight;",
+            "    linkStyle 1 stroke:#f00,stroke-width:3px;",
+        ];
+        assert_eq!(graph.to_mermaid(&options), expected.join("\n"));
+
+        let mut graph = super::Graph::new();
+        graph.add_edge_undirected(1, 1);
+        let mut options = super::ExportOptions {
+            directed: false,
+            ..Default::default()
+        };
+        options.labels.insert(1, "say \"hi\"".to_string());
+        let expected = ["flowchart LR", "    n0[\"say #quot;hi#quot;\"]", "    n0 --- n0"];
+        assert_eq!(graph.to_mermaid(&options), expected.join("\n"));
+        assert!(graph.to_dot(&options).contains("[label=\"say \\\"hi\\\"\"]"));
+
+        options.labels.insert(1, "end\\".to_string());
+        assert!(graph.to_mermaid(&options).contains("n0[\"end\\\"]"));
+    }
+
+    #[test]
+    fn test_from_adjacency_lines() {
+        let input = "a: b c\nb: c\nd:";
+        let graph = super::Graph::from_adjacency_lines(input, ":", " ", true);
+        assert_eq!(graph.get_neighbors("a"), Some(vec!["b", "c"]));
+        assert_eq!(graph.get_neighbors("c"), Some(vec![]));
+        assert_eq!(graph.get_neighbors("d"), Some(vec![]));
+        let graph = super::Graph::from_adjacency_lines(input, ":", " ", false);
+        assert_eq!(graph.get_neighbors("c"), Some(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn test_from_edge_list() {
+        let graph = super::Graph::from_edge_list("kh-tc\nqp-kh\n", "-", false);
+        assert_eq!(graph.get_neighbors("kh"), Some(vec!["tc", "qp"]));
+        let graph = super::Graph::from_edge_list("a,b,2\nb,c,3\na,c,9", ",", true);
+        assert_eq!(graph.dijsktra("a", |node| node == "c"), Some((vec!["c", "b", "a"], 5)));
+        let error = super::Graph::try_from_edge_list("a-b\nc", "-", true).unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (2, "missing separator \"-\""));
+        let error = super::Graph::try_from_edge_list("a,b,1\na,c,x", ",", true).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 5, "x"));
+        assert!(super::Graph::try_from_adjacency_lines("a: b\nc", ":", " ", true).is_err());
+    }
+
+    #[test]
+    fn test_from_map() {
+        let map = super::Map::new("S.#\n.#.\n..E");
+        let graph = super::Graph::from_map(&map, |c| c != '#', |c| if c == 'E' { 10 } else { 1 });
+        assert_eq!(graph.get_neighbors((1, 1)), None);
+        assert_eq!(graph.get_neighbors((0, 0)), Some(vec![(1, 0), (0, 1)]));
+        let end = map.find('E').unwrap();
+        assert_eq!(graph.dijsktra((0, 0), |node| node == end).map(|(_, cost)| cost), Some(13));
+    }
 }
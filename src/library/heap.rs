@@ -0,0 +1,290 @@
+#![allow(dead_code)]
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+struct Entry<P, T> {
+    priority: P,
+    sequence: u64,
+    value: T,
+}
+
+impl<P: Ord, T> PartialEq for Entry<P, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P: Ord, T> Eq for Entry<P, T> {}
+
+impl<P: Ord, T> PartialOrd for Entry<P, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Ord, T> Ord for Entry<P, T> {
+    // Reversed so that `BinaryHeap` pops the lowest priority, then the oldest entry
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Priority queue popping the lowest priority first, holding each value at most once.
+/// Values with the same priority are popped in insertion order.
+///
+/// Pushing a value already in the queue only lowers its priority, the outdated entry
+/// stays in the heap and is skipped when it reaches the top.
+pub struct MinHeap<P, T> {
+    heap: BinaryHeap<Entry<P, T>>,
+    current: HashMap<T, (P, u64)>,
+    sequence: u64,
+}
+
+impl<P, T> MinHeap<P, T>
+where
+    P: Ord + Clone,
+    T: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            current: HashMap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Inserts `value`, or lowers its priority if it is already queued with a higher one.
+    /// Returns whether the queue changed.
+    pub fn push(&mut self, value: T, priority: P) -> bool {
+        if self.priority(&value).is_some_and(|current| *current <= priority) {
+            return false;
+        }
+        self.sequence += 1;
+        self.current.insert(value.clone(), (priority.clone(), self.sequence));
+        self.heap.push(Entry {
+            priority,
+            sequence: self.sequence,
+            value,
+        });
+        self.discard_stale();
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        let Entry { priority, value, .. } = self.heap.pop()?;
+        self.current.remove(&value);
+        self.discard_stale();
+        Some((value, priority))
+    }
+
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.peek().map(|entry| (&entry.value, &entry.priority))
+    }
+
+    /// Current priority of a queued value.
+    pub fn priority(&self, value: &T) -> Option<&P> {
+        self.current.get(value).map(|(priority, _)| priority)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.current.contains_key(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.current.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    fn discard_stale(&mut self) {
+        while let Some(entry) = self.heap.peek() {
+            if self.current.get(&entry.value).is_some_and(|&(_, sequence)| sequence == entry.sequence) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+}
+
+impl<P, T> Default for MinHeap<P, T>
+where
+    P: Ord + Clone,
+    T: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Same as `MinHeap`, popping the highest priority first.
+pub struct MaxHeap<P, T> {
+    heap: MinHeap<Reverse<P>, T>,
+}
+
+impl<P, T> MaxHeap<P, T>
+where
+    P: Ord + Clone,
+    T: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        Self { heap: MinHeap::new() }
+    }
+
+    /// Inserts `value`, or raises its priority if it is already queued with a lower one.
+    /// Returns whether the queue changed.
+    pub fn push(&mut self, value: T, priority: P) -> bool {
+        self.heap.push(value, Reverse(priority))
+    }
+
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        self.heap.pop().map(|(value, Reverse(priority))| (value, priority))
+    }
+
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.peek().map(|(value, Reverse(priority))| (value, priority))
+    }
+
+    pub fn priority(&self, value: &T) -> Option<&P> {
+        self.heap.priority(value).map(|Reverse(priority)| priority)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.heap.contains(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<P, T> Default for MaxHeap<P, T>
+where
+    P: Ord + Clone,
+    T: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Monotone priority queue for small integer priorities, like the costs of a grid Dijkstra.
+/// Popped priorities never decrease, and values with the same priority are popped in insertion order.
+/// Unlike `MinHeap` a value can be queued several times, outdated entries are left to the caller.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    buckets: Vec<VecDeque<T>>,
+    current: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            buckets: Vec::new(),
+            current: 0,
+            len: 0,
+        }
+    }
+
+    /// Panics if `priority` is lower than the last popped priority.
+    pub fn push(&mut self, value: T, priority: usize) {
+        assert!(
+            priority >= self.current,
+            "priority {priority} is lower than the current priority {}",
+            self.current
+        );
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, VecDeque::new);
+        }
+        self.buckets[priority].push_back(value);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(T, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+        while self.buckets[self.current].is_empty() {
+            self.current += 1;
+        }
+        self.len -= 1;
+        let value = self.buckets[self.current].pop_front()?;
+        Some((value, self.current))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap = super::MinHeap::new();
+        assert!(heap.push("a", 5));
+        assert!(heap.push("b", 3));
+        assert!(heap.push("c", 3));
+        assert!(!heap.push("a", 7));
+        assert!(heap.push("a", 1));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.priority(&"a"), Some(&1));
+        assert_eq!(heap.peek(), Some((&"a", &1)));
+        assert_eq!(heap.pop(), Some(("a", 1)));
+        assert_eq!(heap.pop(), Some(("b", 3)));
+        assert!(heap.push("a", 4));
+        assert_eq!(heap.pop(), Some(("c", 3)));
+        assert_eq!(heap.pop(), Some(("a", 4)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = super::MaxHeap::new();
+        heap.push('x', 2);
+        heap.push('y', 8);
+        heap.push('z', 8);
+        heap.push('x', 9);
+        let order = std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>();
+        assert_eq!(order, vec![('x', 9), ('y', 8), ('z', 8)]);
+    }
+
+    #[test]
+    fn test_bucket_queue() {
+        let mut queue = super::BucketQueue::new();
+        queue.push('a', 4);
+        queue.push('b', 1);
+        queue.push('c', 4);
+        assert_eq!(queue.pop(), Some(('b', 1)));
+        queue.push('d', 2);
+        assert_eq!(queue.len(), 3);
+        let order = std::iter::from_fn(|| queue.pop()).collect::<Vec<_>>();
+        assert_eq!(order, vec![('d', 2), ('a', 4), ('c', 4)]);
+        assert!(queue.is_empty());
+    }
+}
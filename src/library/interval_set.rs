@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    fn from_intervals(mut intervals: Vec<(i64, i64)>) -> Self {
+        intervals.retain(|&(start, end)| start <= end);
+        intervals.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(range.into_inner());
+        *self = Self::from_intervals(intervals);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<i64>) {
+        *self = self.difference(&Self::from(range));
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals.get(index).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(|&(start, end)| end - start + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|&(start, _)| start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|&(_, end)| end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals = self.intervals.clone();
+        intervals.extend_from_slice(&other.intervals);
+        Self::from_intervals(intervals)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a_start, a_end) = self.intervals[i];
+            let (b_start, b_end) = other.intervals[j];
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &(start, end) in &self.intervals {
+            while j < other.intervals.len() && other.intervals[j].1 < start {
+                j += 1;
+            }
+            let mut current = start;
+            let mut covered = false;
+            for &(other_start, other_end) in other.intervals[j..].iter().take_while(|&&(other_start, _)| other_start <= end) {
+                if other_start > current {
+                    intervals.push((current, other_start - 1));
+                }
+                if other_end >= end {
+                    covered = true;
+                    break;
+                }
+                current = current.max(other_end + 1);
+            }
+            if !covered {
+                intervals.push((current, end));
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn shift(&self, offset: i64) -> Self {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .map(|&(start, end)| (start + offset, end + offset))
+                .collect(),
+        }
+    }
+
+    /// Moves the parts of the set covered by a source range of `table` by its offset,
+    /// values outside every source range are kept unchanged.
+    pub fn map_offsets(&self, table: &[(RangeInclusive<i64>, i64)]) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = Self::new();
+        for (source, offset) in table {
+            let source = Self::from(source.clone());
+            mapped = mapped.union(&unmapped.intersection(&source).shift(*offset));
+            unmapped = unmapped.difference(&source);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::from_intervals(vec![range.into_inner()])
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(iter: I) -> Self {
+        Self::from_intervals(iter.into_iter().map(RangeInclusive::into_inner).collect())
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_interval_set_merging() {
+        let set = super::IntervalSet::from_iter([10..=14, 3..=5, 16..=20, 12..=18, 6..=6]);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(4));
+        assert!(!set.contains(8));
+        assert!(set.contains(20));
+        assert!(!set.contains(21));
+    }
+
+    #[test]
+    fn test_interval_set_algebra() {
+        let a = super::IntervalSet::from_iter([0..=10, 20..=30]);
+        let b = super::IntervalSet::from_iter([5..=25, 40..=50]);
+        assert_eq!(a.union(&b).ranges().collect::<Vec<_>>(), vec![0..=30, 40..=50]);
+        assert_eq!(a.intersection(&b).ranges().collect::<Vec<_>>(), vec![5..=10, 20..=25]);
+        assert_eq!(a.difference(&b).ranges().collect::<Vec<_>>(), vec![0..=4, 26..=30]);
+        assert_eq!(b.difference(&a).ranges().collect::<Vec<_>>(), vec![11..=19, 40..=50]);
+        let mut c = super::IntervalSet::from(0..=1_000_000_000_000);
+        c.remove(1..=999_999_999_999);
+        assert_eq!(c.ranges().collect::<Vec<_>>(), vec![0..=0, 1_000_000_000_000..=1_000_000_000_000]);
+        c.insert(1..=5);
+        assert_eq!(c.len(), 7);
+    }
+
+    #[test]
+    fn test_interval_set_map_offsets() {
+        // seed-to-soil map: 50 98 2 / 52 50 48
+        let seeds = super::IntervalSet::from_iter([79..=92, 55..=67]);
+        let table = [(98..=99, 50 - 98), (50..=97, 52 - 50)];
+        let soil = seeds.map_offsets(&table);
+        assert_eq!(soil.ranges().collect::<Vec<_>>(), vec![57..=69, 81..=94]);
+        let partial = super::IntervalSet::from(95..=100).map_offsets(&table);
+        assert_eq!(partial.ranges().collect::<Vec<_>>(), vec![50..=51, 97..=100]);
+    }
+}
@@ -0,0 +1,302 @@
+#![allow(dead_code)]
+
+use super::math::gcd;
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Exact fraction of `i128`, always reduced with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "denominator is zero");
+        let sign = denominator.signum();
+        let divisor = gcd(numerator, denominator).max(1);
+        Self {
+            numerator: sign * numerator / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::new(value as i128, 1)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self::new(value, 1)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let divisor = gcd(self.denominator, other.denominator);
+        let numerator = self.numerator * (other.denominator / divisor) + other.numerator * (self.denominator / divisor);
+        Self::new(numerator, self.denominator / divisor * other.denominator)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // Cross-reduce first to keep intermediate values small
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Self::new(
+            (self.numerator / a) * (other.numerator / b),
+            (self.denominator / b) * (other.denominator / a),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "division by zero");
+        let inverse = Self::new(other.denominator, other.numerator);
+        self * inverse
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Vec<Rational>),
+    NoSolution,
+    /// Infinitely many solutions, given with one of them where free variables are zero.
+    Infinite(Vec<Rational>),
+}
+
+/// Solves `matrix * x = rhs` exactly with Gauss-Jordan elimination.
+pub fn solve(matrix: &[Vec<Rational>], rhs: &[Rational]) -> Solution {
+    let rows = matrix.len();
+    let columns = matrix.first().map_or(0, |row| row.len());
+    assert_eq!(rhs.len(), rows, "right-hand side has {} values for {} rows", rhs.len(), rows);
+    assert!(
+        matrix.iter().all(|row| row.len() == columns),
+        "matrix rows must all have {} columns",
+        columns
+    );
+    let mut augmented = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| {
+            let mut row = row.clone();
+            row.push(value);
+            row
+        })
+        .collect::<Vec<Vec<Rational>>>();
+
+    let mut pivots = Vec::new();
+    let mut row = 0;
+    for column in 0..columns {
+        let Some(pivot) = (row..rows).find(|&r| !augmented[r][column].is_zero()) else {
+            continue;
+        };
+        augmented.swap(row, pivot);
+        let factor = augmented[row][column];
+        augmented[row].iter_mut().for_each(|value| *value = *value / factor);
+        let pivot_row = augmented[row].clone();
+        for (other, values) in augmented.iter_mut().enumerate() {
+            let factor = values[column];
+            if other != row && !factor.is_zero() {
+                for (value, &pivot_value) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *value = *value - factor * pivot_value;
+                }
+            }
+        }
+        pivots.push(column);
+        row += 1;
+        if row == rows {
+            break;
+        }
+    }
+
+    if augmented[row..].iter().any(|r| !r[columns].is_zero()) {
+        return Solution::NoSolution;
+    }
+    let mut solution = vec![Rational::ZERO; columns];
+    for (r, &column) in pivots.iter().enumerate() {
+        solution[column] = augmented[r][columns];
+    }
+    if pivots.len() == columns {
+        Solution::Unique(solution)
+    } else {
+        Solution::Infinite(solution)
+    }
+}
+
+/// Same as `solve` for integer coefficients.
+pub fn solve_integers(matrix: &[Vec<i128>], rhs: &[i128]) -> Solution {
+    let matrix = matrix
+        .iter()
+        .map(|row| row.iter().map(|&value| Rational::from(value)).collect())
+        .collect::<Vec<Vec<Rational>>>();
+    let rhs = rhs.iter().map(|&value| Rational::from(value)).collect::<Vec<Rational>>();
+    solve(&matrix, &rhs)
+}
+
+/// Integer solution of `a1 * x + b1 * y = c1` and `a2 * x + b2 * y = c2` with Cramer's rule.
+/// `None` if the solution is not integral or not unique.
+pub fn solve_2x2_integer(a1: i128, b1: i128, c1: i128, a2: i128, b2: i128, c2: i128) -> Option<(i128, i128)> {
+    let determinant = a1 * b2 - a2 * b1;
+    if determinant == 0 {
+        return None;
+    }
+    let x = c1 * b2 - c2 * b1;
+    let y = a1 * c2 - a2 * c1;
+    if x % determinant != 0 || y % determinant != 0 {
+        return None;
+    }
+    Some((x / determinant, y / determinant))
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_rational() {
+        let a = super::Rational::new(6, -4);
+        assert_eq!((a.numerator(), a.denominator()), (-3, 2));
+        let b = super::Rational::new(1, 6);
+        assert_eq!(a + b, super::Rational::new(-4, 3));
+        assert_eq!(a - b, super::Rational::new(-5, 3));
+        assert_eq!(a * b, super::Rational::new(-1, 4));
+        assert_eq!(a / b, super::Rational::from(-9i64));
+        assert_eq!((a / b).to_integer(), Some(-9));
+        assert!(a < b);
+        assert_eq!(format!("{} {}", a, a / b), "-3/2 -9");
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let result = super::solve_integers(&matrix, &[8, -11, -3]);
+        let expected = [2i64, 3, -1].map(super::Rational::from).to_vec();
+        assert_eq!(result, super::Solution::Unique(expected));
+
+        let matrix = vec![vec![1, 1], vec![2, 2]];
+        assert_eq!(super::solve_integers(&matrix, &[1, 3]), super::Solution::NoSolution);
+        let result = super::solve_integers(&matrix, &[1, 2]);
+        let expected = [1i64, 0].map(super::Rational::from).to_vec();
+        assert_eq!(result, super::Solution::Infinite(expected));
+
+        let matrix = vec![vec![2, 0], vec![0, 3]];
+        let result = super::solve_integers(&matrix, &[1, 1]);
+        let expected = vec![super::Rational::new(1, 2), super::Rational::new(1, 3)];
+        assert_eq!(result, super::Solution::Unique(expected));
+    }
+
+    #[test]
+    #[should_panic(expected = "right-hand side has 1 values for 2 rows")]
+    fn test_solve_rhs_mismatch() {
+        super::solve_integers(&[vec![1, 2], vec![3, 4]], &[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix rows must all have 2 columns")]
+    fn test_solve_ragged_matrix() {
+        super::solve_integers(&[vec![1, 2], vec![3]], &[1, 2]);
+    }
+
+    #[test]
+    fn test_solve_2x2_integer() {
+        // Button A: X+94, Y+34 / Button B: X+22, Y+67 / Prize: X=8400, Y=5400
+        assert_eq!(super::solve_2x2_integer(94, 22, 8400, 34, 67, 5400), Some((80, 40)));
+        // Button A: X+26, Y+66 / Button B: X+67, Y+21 / Prize: X=12748, Y=12176
+        assert_eq!(super::solve_2x2_integer(26, 67, 12748, 66, 21, 12176), None);
+        let offset = 10000000000000;
+        assert_eq!(
+            super::solve_2x2_integer(26, 67, 12748 + offset, 66, 21, 12176 + offset),
+            Some((118679050709, 103199174542))
+        );
+        assert_eq!(super::solve_2x2_integer(1, 2, 3, 2, 4, 6), None);
+    }
+}
@@ -0,0 +1,460 @@
+#![allow(dead_code)]
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+/// Integer types supported by the number theory helpers.
+pub trait Integer:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `self * other % modulus` without overflow, for operands in `0..modulus`.
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+
+    /// Remainder in `0..modulus`, also for negative values.
+    fn modulo(self, modulus: Self) -> Self;
+}
+
+macro_rules! impl_integer_widening {
+    ($($ty:ty => $wide:ty),+) => {
+        $(
+            impl Integer for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                    (self as $wide * other as $wide % modulus as $wide) as Self
+                }
+
+                fn modulo(self, modulus: Self) -> Self {
+                    self.rem_euclid(modulus)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_integer_doubling {
+    ($($ty:ty),+) => {
+        $(
+            impl Integer for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                    if let Some(product) = self.checked_mul(other) {
+                        return product % modulus;
+                    }
+                    let (mut a, mut b, mut result) = (self, other, 0);
+                    while b > 0 {
+                        if b % 2 == 1 {
+                            result = add_mod(result, a, modulus);
+                        }
+                        a = add_mod(a, a, modulus);
+                        b /= 2;
+                    }
+                    result
+                }
+
+                fn modulo(self, modulus: Self) -> Self {
+                    self.rem_euclid(modulus)
+                }
+            }
+        )+
+    };
+}
+
+impl_integer_widening!(i32 => i64, u32 => u64, i64 => i128, u64 => u128, isize => i128, usize => u128);
+impl_integer_doubling!(i128, u128);
+
+/// Signed integer types, for the helpers that need negative values.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for isize {}
+impl Signed for i128 {}
+
+fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+fn sub_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        modulus - (b - a)
+    }
+}
+
+fn abs<T: Integer>(value: T) -> T {
+    if value < T::ZERO {
+        T::ZERO - value
+    } else {
+        value
+    }
+}
+
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// Least common multiple of all values, e.g. of cycle lengths, `1` for no values.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Inverse of `a` modulo `modulus`, `None` if they are not coprime.
+pub fn mod_inv<T: Integer>(a: T, modulus: T) -> Option<T> {
+    // Coefficients are kept modulo `modulus` so that unsigned types work too
+    let (mut old_r, mut r) = (a.modulo(modulus), modulus);
+    let (mut old_s, mut s) = (T::ONE.modulo(modulus), T::ZERO);
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        let product = quotient.modulo(modulus).mul_mod(s, modulus);
+        (old_s, s) = (s, sub_mod(old_s, product, modulus));
+    }
+    (old_r == T::ONE).then_some(old_s)
+}
+
+pub fn mod_pow<T: Integer>(base: T, exponent: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let mut base = base.modulo(modulus);
+    let mut exponent = exponent;
+    let mut result = T::ONE.modulo(modulus);
+    while exponent > T::ZERO {
+        if exponent % two == T::ONE {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exponent = exponent / two;
+    }
+    result
+}
+
+/// Chinese Remainder Theorem over `(residue, modulus)` congruences, moduli need not be coprime.
+/// Returns the smallest non-negative solution with the combined modulus, `None` if the
+/// congruences are inconsistent.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut result = T::ZERO;
+    let mut modulus = T::ONE;
+    for &(residue, other_modulus) in congruences {
+        let residue = residue.modulo(other_modulus);
+        let g = gcd(modulus, other_modulus);
+        let difference = sub_mod(residue, result.modulo(other_modulus), other_modulus);
+        if difference % g != T::ZERO {
+            return None;
+        }
+        let reduced = other_modulus / g;
+        let k = if reduced == T::ONE {
+            T::ZERO
+        } else {
+            let inverse = mod_inv((modulus / g).modulo(reduced), reduced)?;
+            (difference / g).modulo(reduced).mul_mod(inverse, reduced)
+        };
+        result = result + modulus * k;
+        modulus = modulus * reduced;
+    }
+    Some((result, modulus))
+}
+
+/// Integer modulo `M`, products are computed in `u128` so any `M < 2^64` is safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: i128) -> Self {
+        Self {
+            value: value.rem_euclid(M as i128) as u64,
+        }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self {
+            value: mod_pow(self.value, exponent, M),
+        }
+    }
+
+    /// `None` if the value is not coprime with `M`.
+    pub fn inverse(self) -> Option<Self> {
+        mod_inv(self.value, M).map(|value| Self { value })
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self { value: value % M }
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        Self::new(value as i128)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: add_mod(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            value: sub_mod(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            value: self.value.mul_mod(other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// Panics if `other` is not invertible modulo `M`.
+    fn div(self, other: Self) -> Self {
+        let inverse = other.inverse().expect("divisor is not invertible");
+        Self {
+            value: self.value.mul_mod(inverse.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::default() - self
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// The map `x -> a * x + b` modulo `M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AffineMap<const M: u64> {
+    pub a: ModInt<M>,
+    pub b: ModInt<M>,
+}
+
+impl<const M: u64> AffineMap<M> {
+    pub fn new(a: i128, b: i128) -> Self {
+        Self {
+            a: ModInt::new(a),
+            b: ModInt::new(b),
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1, 0)
+    }
+
+    pub fn apply(&self, x: ModInt<M>) -> ModInt<M> {
+        self.a * x + self.b
+    }
+
+    /// The map applying `self` first and then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            a: self.a * other.a,
+            b: other.a * self.b + other.b,
+        }
+    }
+
+    /// `self` applied `times` times in a row.
+    pub fn pow(&self, times: u64) -> Self {
+        let mut result = Self::identity();
+        let mut base = *self;
+        let mut times = times;
+        while times > 0 {
+            if times % 2 == 1 {
+                result = result.then(&base);
+            }
+            base = base.then(&base);
+            times /= 2;
+        }
+        result
+    }
+
+    /// `None` if `a` is not invertible modulo `M`.
+    pub fn inverse(&self) -> Option<Self> {
+        let a = self.a.inverse()?;
+        Some(Self { a, b: -(self.b * a) })
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(super::gcd(12i64, -18), 6);
+        assert_eq!(super::gcd(0u64, 7), 7);
+        assert_eq!(super::lcm(4i128, 6), 12);
+        assert_eq!(super::lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(super::gcd_all([12i64, 18, 30]), 6);
+        assert_eq!(super::lcm_all(Vec::<i64>::new()), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = super::extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_mod_inv_and_pow() {
+        assert_eq!(super::mod_inv(3i64, 11), Some(4));
+        assert_eq!(super::mod_inv(-3i64, 11), Some(7));
+        assert_eq!(super::mod_inv(6u64, 9), None);
+        assert_eq!(super::mod_inv(3u64, 11), Some(4));
+        assert_eq!(super::mod_pow(2u64, 10, 1000), 24);
+        let p = 119315717514047i128;
+        assert_eq!(super::mod_pow(12345i128, p - 1, p), 1);
+        let big = 18446744073709551557u64; // largest 64-bit prime
+        assert_eq!(super::mod_pow(2u64, big - 1, big), 1);
+        let inverse = super::mod_inv(123456789u64, big).unwrap();
+        assert_eq!(super::Integer::mul_mod(inverse, 123456789, big), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        // Bus schedule 7,13,x,x,59,x,31,19
+        let buses = [(7i64, 0i64), (13, 1), (59, 4), (31, 6), (19, 7)];
+        let congruences = buses.map(|(id, offset)| (-offset, id));
+        assert_eq!(super::crt(&congruences), Some((1068781, 7 * 13 * 59 * 31 * 19)));
+        assert_eq!(super::crt(&[(2u64, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(super::crt(&[(1i64, 6), (2, 4)]), None);
+        assert_eq!(super::crt::<i128>(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_mod_int() {
+        type M = super::ModInt<119315717514047>;
+        let a = M::new(-1);
+        assert_eq!(a.value(), 119315717514046);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!((a + M::from(2u64)).value(), 1);
+        let x = M::from(123456789123i64);
+        assert_eq!((x / x).value(), 1);
+        assert_eq!((x * x.inverse().unwrap()).value(), 1);
+        assert_eq!(x.pow(119315717514046).value(), 1);
+        let mut y = M::from(5u64);
+        y -= M::from(7u64);
+        y *= M::from(3u64);
+        assert_eq!(y, M::new(-6));
+    }
+
+    #[test]
+    fn test_affine_map_shuffle() {
+        // deal with increment 7, deal into new stack, deal into new stack
+        let increment = super::AffineMap::<10>::new(7, 0);
+        let new_stack = super::AffineMap::<10>::new(-1, -1);
+        let shuffle = increment.then(&new_stack).then(&new_stack);
+        let mut deck = [0; 10];
+        for card in 0..10u64 {
+            deck[shuffle.apply(card.into()).value() as usize] = card;
+        }
+        assert_eq!(deck, [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+
+        type Shuffle = super::AffineMap<119315717514047>;
+        let shuffle = Shuffle::new(-7, 0).then(&Shuffle::new(1, -3)).then(&Shuffle::new(-1, -1));
+        let repeated = shuffle.pow(101741582076661);
+        let undone = repeated.then(&repeated.inverse().unwrap());
+        assert_eq!(undone, Shuffle::identity());
+        assert_eq!(shuffle.pow(3), shuffle.then(&shuffle).then(&shuffle));
+    }
+}
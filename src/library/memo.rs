@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// Cache for a recursive function, keyed by its arguments.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Cached value for `key`, computed with `compute` on a miss.
+    /// `compute` gets the memo back so it can recurse through it.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Empties the cache and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.entries,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_memo_fibonacci() {
+        fn fibonacci(memo: &mut super::Memo<u64, u64>, n: u64) -> u64 {
+            memo.get_or_compute(n, |memo| match n {
+                0 | 1 => n,
+                _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+            })
+        }
+
+        let mut memo = super::Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.stats().misses, 91);
+        assert_eq!(memo.get(&10), Some(&55));
+    }
+
+    #[test]
+    fn test_memo_blinking_stones() {
+        fn count(memo: &mut super::Memo<(u64, usize), u64>, stone: u64, blinks: usize) -> u64 {
+            if blinks == 0 {
+                return 1;
+            }
+            memo.get_or_compute((stone, blinks), |memo| {
+                let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+                if stone == 0 {
+                    count(memo, 1, blinks - 1)
+                } else if digits.is_multiple_of(2) {
+                    let half = 10u64.pow(digits / 2);
+                    count(memo, stone / half, blinks - 1) + count(memo, stone % half, blinks - 1)
+                } else {
+                    count(memo, stone * 2024, blinks - 1)
+                }
+            })
+        }
+
+        let mut memo = super::Memo::new();
+        let total = [125, 17].iter().map(|&stone| count(&mut memo, stone, 25)).sum::<u64>();
+        assert_eq!(total, 55312);
+        let stats = memo.stats();
+        assert_eq!(stats.misses, stats.entries);
+        assert!(stats.hits > 0);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().to_string(), "0 hits, 0 misses, 0 entries (0.0% hit rate)");
+    }
+}
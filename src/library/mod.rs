@@ -1,4 +1,16 @@
 pub mod parser;
 pub mod map;
 pub mod graph;
-pub mod lib;
\ No newline at end of file
+pub mod heap;
+pub mod union_find;
+pub mod assignment;
+pub mod interval_set;
+pub mod math;
+pub mod linear;
+pub mod geometry;
+pub mod sequence;
+pub mod cycle;
+pub mod memo;
+pub mod bitset;
+pub mod fenwick;
+pub mod segment_tree;
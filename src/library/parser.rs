@@ -1,15 +1,107 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, num::ParseIntError, ops::RangeInclusive, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text, 0 when unknown.
+    pub line: usize,
+    /// 1-based column of the offending text, 0 when unknown.
+    pub column: usize,
+    /// 1-based row and token index in values that were already split, for errors
+    /// that have no position in the input.
+    pub token: Option<(usize, usize)>,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Locates `fragment`, which must be a slice of `input`, to report its line and column.
+    pub fn new(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize).saturating_sub(start).min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            token: None,
+            text: fragment.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            token: None,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error on the `token`-th value of the `row`-th row of already split values.
+    pub fn at_token(row: usize, token: usize, text: &str, message: impl Into<String>) -> Self {
+        Self {
+            token: Some((row, token)),
+            ..Self::at(0, 0, text, message)
+        }
+    }
+
+    /// Moves an error located inside `fragment` to its position in `input`.
+    pub fn relocate(mut self, input: &str, fragment: &str) -> Self {
+        let base = ParseError::new(input, fragment, "");
+        if self.line == 0 {
+            self.line = base.line;
+            self.column = base.column;
+        } else {
+            if self.line == 1 {
+                self.column += base.column - 1;
+            }
+            self.line += base.line - 1;
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        } else if let Some((row, token)) = self.token {
+            write!(f, "row {}, token {}: ", row, token)?;
+        }
+        write!(f, "{} in {:?}", self.message, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn try_to_single_line(input: &str) -> Result<&str, ParseError> {
+    input
+        .trim()
+        .lines()
+        .next()
+        .map(|line| line.trim())
+        .ok_or_else(|| ParseError::new(input, input, "empty input"))
+}
 
 pub fn to_single_line(input: &str) -> &str {
-    input.trim().lines().next().unwrap().trim()
+    try_to_single_line(input).unwrap_or_else(|error| panic!("{}", error))
 }
 
 pub fn to_column(input: &str) -> Vec<&str> {
     input.trim().lines().map(|line| line.trim()).collect()
 }
 
+pub fn try_to_map(input: &str) -> Result<super::map::Map, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::new(input, input, "empty input"));
+    }
+    Ok(super::map::Map::new(input))
+}
+
 pub fn to_map(input: &str) -> super::map::Map {
-    super::map::Map::new(input)
+    try_to_map(input).unwrap_or_else(|error| panic!("{}", error))
 }
 
 pub fn to_matrix<'a>(input: &'a str, split_pattern: &'a str) -> Vec<Vec<&'a str>> {
@@ -20,92 +112,1160 @@ pub fn to_matrix<'a>(input: &'a str, split_pattern: &'a str) -> Vec<Vec<&'a str>
         .collect()
 }
 
+fn try_split_key_values<'a>(
+    input: &'a str,
+    line: &'a str,
+    split_pattern_key: &'a str,
+    split_pattern_value: &'a str,
+) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+    let mut parts = line.splitn(2, split_pattern_key);
+    let key = parts.next().unwrap_or_default().trim();
+    let value = parts
+        .next()
+        .ok_or_else(|| ParseError::new(input, line, format!("missing separator {:?}", split_pattern_key)))?
+        .trim()
+        .split(split_pattern_value)
+        .collect();
+    Ok((key, value))
+}
+
+pub fn try_to_dict<'a>(
+    input: &'a str,
+    split_pattern_key: &'a str,
+    split_pattern_value: &'a str,
+) -> Result<HashMap<&'a str, Vec<&'a str>>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| try_split_key_values(input, line, split_pattern_key, split_pattern_value))
+        .collect()
+}
+
 pub fn to_dict<'a>(
     input: &'a str,
     split_pattern_key: &'a str,
     split_pattern_value: &'a str,
 ) -> HashMap<&'a str, Vec<&'a str>> {
+    try_to_dict(input, split_pattern_key, split_pattern_value).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_to_tuples<'a>(
+    input: &'a str,
+    split_pattern_key: &'a str,
+    split_pattern_value: &'a str,
+) -> Result<Vec<(&'a str, Vec<&'a str>)>, ParseError> {
     input
         .trim()
         .lines()
-        .map(|line| {
-            let mut parts = line.splitn(2, split_pattern_key);
-            let key = parts.next().unwrap().trim();
-            let value = parts
-                .next()
-                .unwrap()
-                .trim()
-                .split(split_pattern_value)
-                .collect();
-            (key, value)
-        })
+        .map(|line| try_split_key_values(input, line, split_pattern_key, split_pattern_value))
         .collect()
 }
 
 pub fn to_tuples<'a>(input: &'a str, split_pattern_key: &'a str, split_pattern_value: &'a str) -> Vec<(&'a str, Vec<&'a str>)> {
+    try_to_tuples(input, split_pattern_key, split_pattern_value).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Blocks of lines separated by blank lines, trailing whitespace and `\r` are stripped.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some((start, end)) = current.take() {
+                sections.push(&input[start..end]);
+            }
+        } else {
+            let start = current.map_or(offset, |(start, _)| start);
+            current = Some((start, offset + line.trim_end().len()));
+        }
+        offset += line.len();
+    }
+    if let Some((start, end)) = current {
+        sections.push(&input[start..end]);
+    }
+    sections
+}
+
+pub fn try_sections_n<const N: usize>(input: &str) -> Result<[&str; N], ParseError> {
+    let sections = sections(input);
+    let count = sections.len();
+    sections
+        .try_into()
+        .map_err(|_| ParseError::new(input, input, format!("expected {} sections, found {}", N, count)))
+}
+
+pub fn sections_n<const N: usize>(input: &str) -> [&str; N] {
+    try_sections_n(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_two_sections(input: &str) -> Result<(&str, &str), ParseError> {
+    let [first, second] = try_sections_n(input)?;
+    Ok((first, second))
+}
+
+pub fn two_sections(input: &str) -> (&str, &str) {
+    try_two_sections(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Inclusive ranges written `a-b`, separated by commas or whitespace, e.g. `11-22,95-115`.
+pub fn try_ranges(input: &str) -> Result<Vec<RangeInclusive<i64>>, ParseError> {
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            // Skip the first character so that a negative start is not taken as the separator
+            let separator = token
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '-')
+                .map(|(i, _)| i)
+                .ok_or_else(|| ParseError::new(input, token, "expected a range \"a-b\""))?;
+            let start = token[..separator]
+                .parse()
+                .map_err(|error: ParseIntError| ParseError::new(input, token, error.to_string()))?;
+            let end = token[separator + 1..]
+                .parse()
+                .map_err(|error: ParseIntError| ParseError::new(input, token, error.to_string()))?;
+            Ok(start..=end)
+        })
+        .collect()
+}
+
+pub fn ranges(input: &str) -> Vec<RangeInclusive<i64>> {
+    try_ranges(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Ranges given as `start length` pairs of integers, e.g. `seeds: 79 14 55 13`.
+pub fn try_length_ranges(input: &str) -> Result<Vec<RangeInclusive<i64>>, ParseError> {
+    let values = try_ints::<i64>(input)?;
+    if values.len() % 2 != 0 {
+        return Err(ParseError::new(input, input, "expected start and length pairs"));
+    }
+    Ok(values
+        .chunks_exact(2)
+        .map(|pair| pair[0]..=pair[0] + pair[1] - 1)
+        .collect())
+}
+
+pub fn length_ranges(input: &str) -> Vec<RangeInclusive<i64>> {
+    try_length_ranges(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+fn number_tokens(line: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            if signed && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+            }
+            if bytes[i].is_ascii_digit() {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(&line[start..i]);
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+/// Errors are located relative to `source`, which must contain every token.
+fn parse_tokens<'a, T>(source: &'a str, tokens: impl Iterator<Item = &'a str>) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    tokens
+        .map(|token| token.parse().map_err(|error: T::Err| ParseError::new(source, token, error.to_string())))
+        .collect()
+}
+
+fn to_array<T, const N: usize>(line: &str, values: Vec<T>) -> Result<[T; N], ParseError> {
+    let count = values.len();
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(line, line, format!("expected {} numbers, found {}", N, count)))
+}
+
+/// Every integer in `line`, a `-` directly before digits is read as a sign.
+pub fn try_ints<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_tokens(line, number_tokens(line, true))
+}
+
+pub fn ints<T>(line: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_ints(line).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Every digit run in `line`, signs are ignored so `3-5` gives `3` and `5`.
+pub fn try_uints<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_tokens(line, number_tokens(line, false))
+}
+
+pub fn uints<T>(line: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_uints(line).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as `try_ints` for every line, errors are located in `input`.
+pub fn try_ints_per_line<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
     input
         .trim()
         .lines()
-        .map(|line| {
-            let mut parts = line.splitn(2, split_pattern_key);
-            let key = parts.next().unwrap().trim();
-            let value = parts
-                .next()
-                .unwrap()
-                .trim()
-                .split(split_pattern_value)
-                .collect();
-            (key, value)
-        })
+        .map(|line| parse_tokens(input, number_tokens(line, true)))
         .collect()
 }
 
+pub fn ints_per_line<T>(input: &str) -> Vec<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_ints_per_line(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as `try_uints` for every line, errors are located in `input`.
+pub fn try_uints_per_line<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    input
+        .trim()
+        .lines()
+        .map(|line| parse_tokens(input, number_tokens(line, false)))
+        .collect()
+}
+
+pub fn uints_per_line<T>(input: &str) -> Vec<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_uints_per_line(input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_ints_n<T, const N: usize>(line: &str) -> Result<[T; N], ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    to_array(line, try_ints(line)?)
+}
+
+pub fn ints_n<T, const N: usize>(line: &str) -> [T; N]
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_ints_n(line).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_uints_n<T, const N: usize>(line: &str) -> Result<[T; N], ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    to_array(line, try_uints(line)?)
+}
+
+pub fn uints_n<T, const N: usize>(line: &str) -> [T; N]
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    try_uints_n(line).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Splits `line` around the literal parts of `pattern`, returning the text matched by each `{}`.
+/// Errors are located relative to `source`, which must contain `line`.
+pub fn match_pattern<'a>(source: &'a str, pattern: &str, line: &'a str, expected: usize) -> Result<Vec<&'a str>, ParseError> {
+    let mut literals = pattern.split("{}");
+    let placeholders = pattern.matches("{}").count();
+    if placeholders != expected {
+        let message = format!("pattern has {} placeholders for {} types", placeholders, expected);
+        return Err(ParseError::at(0, 0, pattern, message));
+    }
+    let expect_literal = |rest: &'a str, literal: &str| ParseError::new(source, rest, format!("expected {:?}", literal));
+
+    let prefix = literals.next().unwrap_or_default();
+    let mut rest = line.strip_prefix(prefix).ok_or_else(|| expect_literal(line, prefix))?;
+    let mut captures = Vec::with_capacity(expected);
+    for literal in literals {
+        if literal.is_empty() {
+            captures.push(rest);
+            rest = &rest[rest.len()..];
+            continue;
+        }
+        let position = rest.find(literal).ok_or_else(|| expect_literal(rest, literal))?;
+        captures.push(&rest[..position]);
+        rest = &rest[position + literal.len()..];
+    }
+    if !rest.trim().is_empty() {
+        return Err(ParseError::new(source, rest, "unexpected trailing text"));
+    }
+    Ok(captures)
+}
+
+pub fn parse_capture<T>(source: &str, capture: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let capture = capture.trim();
+    capture
+        .parse()
+        .map_err(|error: T::Err| ParseError::new(source, capture, error.to_string()))
+}
+
+/// Parses a line against a pattern where each `{}` is a typed field.
+///
+/// `parse_pattern!("#{} @ {},{}: {}x{}", line => usize, i64, i64, i64, i64)` returns a
+/// `Result` of a tuple, and `parse_pattern!(pattern, lines input => ...)` one for each line of `input`.
+#[macro_export]
+macro_rules! parse_pattern {
+    (@parse $pattern:expr, $source:expr, $line:expr => $($ty:ty),+) => {
+        'parse: {
+            let count = [$(stringify!($ty)),+].len();
+            let mut captures = match $crate::parser::match_pattern($source, $pattern, $line, count) {
+                Ok(captures) => captures.into_iter(),
+                Err(error) => break 'parse Err(error),
+            };
+            Ok(($(
+                match $crate::parser::parse_capture::<$ty>($source, captures.next().unwrap()) {
+                    Ok(value) => value,
+                    Err(error) => break 'parse Err(error),
+                },
+            )+))
+        }
+    };
+    ($pattern:expr, lines $input:expr => $($ty:ty),+ $(,)?) => {{
+        let input: &str = $input;
+        input
+            .trim()
+            .lines()
+            .map(|line| $crate::parse_pattern!(@parse $pattern, input, line => $($ty),+))
+            .collect::<Result<Vec<_>, $crate::parser::ParseError>>()
+    }};
+    ($pattern:expr, $line:expr => $($ty:ty),+ $(,)?) => {{
+        let line: &str = $line;
+        $crate::parse_pattern!(@parse $pattern, line, line => $($ty),+)
+    }};
+}
+
+pub use crate::parse_pattern;
+
+/// A value that can be read from a line or a section of the input.
+///
+/// Implemented for numbers, `char`, `String`, `bool`, `Map`, and `Vec<T>` of whitespace or comma
+/// separated values. The `from_input!` macro implements it for structs and enums described by a pattern.
+pub trait FromInput: Sized {
+    fn from_input(input: &str) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_input_from_str {
+    ($($ty:ty),+) => {
+        $(
+            impl FromInput for $ty {
+                fn from_input(input: &str) -> Result<Self, ParseError> {
+                    input.parse().map_err(|error: <$ty as FromStr>::Err| ParseError::new(input, input, error.to_string()))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_input_from_str!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, bool, String);
+
+impl<T> FromInput for Vec<T>
+where
+    T: FromInput,
+{
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(|token| from_capture(input, token))
+            .collect()
+    }
+}
+
+impl FromInput for super::map::Map {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        try_to_map(input)
+    }
+}
+
+pub fn from_capture<T>(source: &str, capture: &str) -> Result<T, ParseError>
+where
+    T: FromInput,
+{
+    let capture = capture.trim();
+    T::from_input(capture).map_err(|error| error.relocate(source, capture))
+}
+
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromInput,
+{
+    input.trim().lines().map(|line| from_capture(input, line)).collect()
+}
+
+pub fn parse_sections<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromInput,
+{
+    sections(input).into_iter().map(|section| from_capture(input, section)).collect()
+}
+
+/// Declares a struct or an enum and implements `FromInput` for it from a `parse_pattern!` style pattern.
+///
+/// ```ignore
+/// from_input! {
+///     struct Equation = "{}: {}" { target: u64, numbers: Vec<u64> }
+/// }
+/// from_input! {
+///     enum Instruction { Mul(i64, i64) = "mul({},{})", Do = "do()" }
+/// }
+/// ```
+#[macro_export]
+macro_rules! from_input {
+    (@variant $input:ident, $pattern:literal, $variant:ident ( $($ty:ty),+ )) => {
+        'parse: {
+            let count = [$(stringify!($ty)),+].len();
+            let mut captures = match $crate::parser::match_pattern($input, $pattern, $input, count) {
+                Ok(captures) => captures.into_iter(),
+                Err(error) => break 'parse Err(error),
+            };
+            Ok(Self::$variant($(
+                match $crate::parser::from_capture::<$ty>($input, captures.next().unwrap()) {
+                    Ok(value) => value,
+                    Err(error) => break 'parse Err(error),
+                },
+            )+))
+        }
+    };
+    (@variant $input:ident, $pattern:literal, $variant:ident) => {
+        $crate::parser::match_pattern($input, $pattern, $input, 0).map(|_| Self::$variant)
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $pattern:literal {
+            $($field_vis:vis $field:ident : $ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $ty),+
+        }
+
+        impl $crate::parser::FromInput for $name {
+            fn from_input(input: &str) -> Result<Self, $crate::parser::ParseError> {
+                let count = [$(stringify!($field)),+].len();
+                let mut captures = $crate::parser::match_pattern(input, $pattern, input, count)?.into_iter();
+                Ok(Self {
+                    $($field: $crate::parser::from_capture(input, captures.next().unwrap())?),+
+                })
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident $(( $($ty:ty),+ ))? = $pattern:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant $(( $($ty),+ ))?),+
+        }
+
+        impl $crate::parser::FromInput for $name {
+            // Reports the error of the variant that matched the furthest.
+            fn from_input(input: &str) -> Result<Self, $crate::parser::ParseError> {
+                let mut furthest: Option<$crate::parser::ParseError> = None;
+                $(
+                    match $crate::from_input!(@variant input, $pattern, $variant $(( $($ty),+ ))?) {
+                        Ok(value) => return Ok(value),
+                        Err(error) => {
+                            if furthest.as_ref().map_or(true, |f| (error.line, error.column) > (f.line, f.column)) {
+                                furthest = Some(error);
+                            }
+                        }
+                    }
+                )+
+                Err(furthest.unwrap())
+            }
+        }
+    };
+}
+
+pub use crate::from_input;
+
+/// Allocation-free cursor over the input bytes for hot parsing loops.
+#[derive(Debug, Clone)]
+pub struct ByteParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteParser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::from_bytes(input.as_bytes())
+    }
+
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    #[inline]
+    pub fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    pub fn skip(&mut self, count: usize) {
+        self.position = (self.position + count).min(self.bytes.len());
+    }
+
+    /// Skips spaces, tabs and line breaks.
+    #[inline]
+    pub fn skip_ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    /// Skips bytes until the next digit, or `-` followed by a digit.
+    #[inline]
+    pub fn skip_to_number(&mut self) {
+        while let Some(byte) = self.peek() {
+            let next_is_digit = self.bytes.get(self.position + 1).is_some_and(u8::is_ascii_digit);
+            if byte.is_ascii_digit() || (byte == b'-' && next_is_digit) {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Reads an unsigned integer, `None` if the cursor is not on a digit.
+    /// A number that overflows is consumed and gives `None` as well.
+    #[inline]
+    pub fn u64(&mut self) -> Option<u64> {
+        let start = self.position;
+        let mut value = Some(0u64);
+        while let Some(byte @ b'0'..=b'9') = self.peek() {
+            value = value.and_then(|value| value.checked_mul(10)?.checked_add((byte - b'0') as u64));
+            self.position += 1;
+        }
+        if self.position == start {
+            return None;
+        }
+        value
+    }
+
+    #[inline]
+    pub fn usize(&mut self) -> Option<usize> {
+        self.u64().and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// Reads an integer with an optional `-` or `+` sign.
+    #[inline]
+    pub fn i64(&mut self) -> Option<i64> {
+        let start = self.position;
+        let negative = match self.peek() {
+            Some(b'-') => true,
+            Some(b'+') => false,
+            _ => return self.u64().and_then(|value| i64::try_from(value).ok()),
+        };
+        self.position += 1;
+        if !self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.position = start;
+            return None;
+        }
+        let value = self.u64()?;
+        if negative {
+            0i64.checked_sub_unsigned(value)
+        } else {
+            i64::try_from(value).ok()
+        }
+    }
+
+    /// Returns the bytes before `delimiter` and moves past it, or the rest of the input.
+    #[inline]
+    pub fn until(&mut self, delimiter: u8) -> &'a [u8] {
+        let rest = self.remaining();
+        match rest.iter().position(|&byte| byte == delimiter) {
+            Some(length) => {
+                self.position += length + 1;
+                &rest[..length]
+            }
+            None => {
+                self.position = self.bytes.len();
+                rest
+            }
+        }
+    }
+
+    /// Moves past `prefix` if the input continues with it.
+    #[inline]
+    pub fn eat(&mut self, prefix: &[u8]) -> bool {
+        if self.remaining().starts_with(prefix) {
+            self.position += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves past `prefix`, panics with the position if the input does not continue with it.
+    #[inline]
+    pub fn expect(&mut self, prefix: &[u8]) {
+        if !self.eat(prefix) {
+            let found = &self.remaining()[..prefix.len().min(self.remaining().len())];
+            panic!(
+                "expected {:?} at byte {}, found {:?}",
+                String::from_utf8_lossy(prefix),
+                self.position,
+                String::from_utf8_lossy(found)
+            );
+        }
+    }
+
+    /// Returns the rest of the current line without its line break, `None` at the end of the input.
+    #[inline]
+    pub fn line(&mut self) -> Option<&'a [u8]> {
+        if self.is_empty() {
+            return None;
+        }
+        let line = self.until(b'\n');
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Small parser combinators for nested formats.
+///
+/// Parsers are closures or functions `&str -> ParseResult`, recursive grammars are written as
+/// named functions calling themselves or with `recursive`.
+pub mod combinator {
+
+    use super::ParseError;
+    use std::{
+        cell::OnceCell,
+        fmt,
+        rc::{Rc, Weak},
+        str::FromStr,
+    };
+
+    /// Where a parser failed and what it expected there.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Failure<'a> {
+        pub rest: &'a str,
+        pub expected: String,
+    }
+
+    impl<'a> Failure<'a> {
+        pub fn new(rest: &'a str, expected: impl Into<String>) -> Self {
+            Self {
+                rest,
+                expected: expected.into(),
+            }
+        }
+
+        pub fn to_parse_error(&self, input: &str) -> ParseError {
+            let rest = &self.rest[..self.rest.find('\n').unwrap_or(self.rest.len())];
+            ParseError::new(input, rest, format!("expected {}", self.expected))
+        }
+    }
+
+    /// The parsed value and the remaining input.
+    pub type ParseResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+    pub trait Parser<'a, T> {
+        fn parse(&self, input: &'a str) -> ParseResult<'a, T>;
+    }
+
+    impl<'a, T, F> Parser<'a, T> for F
+    where
+        F: Fn(&'a str) -> ParseResult<'a, T>,
+    {
+        fn parse(&self, input: &'a str) -> ParseResult<'a, T> {
+            self(input)
+        }
+    }
+
+    /// Runs `parser` on the whole input, only trailing whitespace may be left.
+    pub fn parse_all<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+        match parser.parse(input) {
+            Ok((value, rest)) if rest.trim().is_empty() => Ok(value),
+            Ok((_, rest)) => Err(Failure::new(rest, "end of input").to_parse_error(input)),
+            Err(failure) => Err(failure.to_parse_error(input)),
+        }
+    }
+
+    pub fn tag<'a>(literal: &'a str) -> impl Fn(&'a str) -> ParseResult<'a, &'a str> {
+        move |input: &'a str| match input.strip_prefix(literal) {
+            Some(rest) => Ok((&input[..literal.len()], rest)),
+            None => Err(Failure::new(input, format!("{:?}", literal))),
+        }
+    }
+
+    /// An optionally negative integer.
+    pub fn number<'a, T>() -> impl Fn(&'a str) -> ParseResult<'a, T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        move |input: &'a str| {
+            let sign = usize::from(input.starts_with('-'));
+            let digits = input[sign..].bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return Err(Failure::new(input, "number"));
+            }
+            let (number, rest) = input.split_at(sign + digits);
+            number
+                .parse()
+                .map(|value| (value, rest))
+                .map_err(|error: T::Err| Failure::new(input, format!("number ({})", error)))
+        }
+    }
+
+    /// At least one character matching `predicate`.
+    pub fn take_while1<'a, P>(predicate: P) -> impl Fn(&'a str) -> ParseResult<'a, &'a str>
+    where
+        P: Fn(char) -> bool,
+    {
+        move |input: &'a str| {
+            let end = input.find(|c: char| !predicate(c)).unwrap_or(input.len());
+            if end == 0 {
+                return Err(Failure::new(input, "matching character"));
+            }
+            Ok((&input[..end], &input[end..]))
+        }
+    }
+
+    pub fn map<'a, T, U, P, F>(parser: P, f: F) -> impl Fn(&'a str) -> ParseResult<'a, U>
+    where
+        P: Parser<'a, T>,
+        F: Fn(T) -> U,
+    {
+        move |input: &'a str| parser.parse(input).map(|(value, rest)| (f(value), rest))
+    }
+
+    pub fn opt<'a, T, P>(parser: P) -> impl Fn(&'a str) -> ParseResult<'a, Option<T>>
+    where
+        P: Parser<'a, T>,
+    {
+        move |input: &'a str| match parser.parse(input) {
+            Ok((value, rest)) => Ok((Some(value), rest)),
+            Err(_) => Ok((None, input)),
+        }
+    }
+
+    pub fn pair<'a, T, U, P, Q>(first: P, second: Q) -> impl Fn(&'a str) -> ParseResult<'a, (T, U)>
+    where
+        P: Parser<'a, T>,
+        Q: Parser<'a, U>,
+    {
+        move |input: &'a str| {
+            let (a, rest) = first.parse(input)?;
+            let (b, rest) = second.parse(rest)?;
+            Ok(((a, b), rest))
+        }
+    }
+
+    pub fn preceded<'a, T, U, P, Q>(prefix: P, parser: Q) -> impl Fn(&'a str) -> ParseResult<'a, U>
+    where
+        P: Parser<'a, T>,
+        Q: Parser<'a, U>,
+    {
+        map(pair(prefix, parser), |(_, value)| value)
+    }
+
+    pub fn terminated<'a, T, U, P, Q>(parser: P, suffix: Q) -> impl Fn(&'a str) -> ParseResult<'a, T>
+    where
+        P: Parser<'a, T>,
+        Q: Parser<'a, U>,
+    {
+        map(pair(parser, suffix), |(value, _)| value)
+    }
+
+    pub fn delimited<'a, T, U, V, P, Q, R>(open: P, parser: Q, close: R) -> impl Fn(&'a str) -> ParseResult<'a, U>
+    where
+        P: Parser<'a, T>,
+        Q: Parser<'a, U>,
+        R: Parser<'a, V>,
+    {
+        preceded(open, terminated(parser, close))
+    }
+
+    /// Zero or more items separated by `separator`.
+    pub fn separated_list<'a, T, U, P, S>(item: P, separator: S) -> impl Fn(&'a str) -> ParseResult<'a, Vec<T>>
+    where
+        P: Parser<'a, T>,
+        S: Parser<'a, U>,
+    {
+        move |input: &'a str| {
+            let mut items = Vec::new();
+            let mut rest = match item.parse(input) {
+                Ok((value, rest)) => {
+                    items.push(value);
+                    rest
+                }
+                Err(_) => return Ok((items, input)),
+            };
+            while let Ok((_, after_separator)) = separator.parse(rest) {
+                let (value, after_item) = item.parse(after_separator)?;
+                items.push(value);
+                rest = after_item;
+            }
+            Ok((items, rest))
+        }
+    }
+
+    /// Zero or more repetitions of `parser`.
+    pub fn many<'a, T, P>(parser: P) -> impl Fn(&'a str) -> ParseResult<'a, Vec<T>>
+    where
+        P: Parser<'a, T>,
+    {
+        move |input: &'a str| {
+            let mut items = Vec::new();
+            let mut rest = input;
+            while let Ok((value, next)) = parser.parse(rest) {
+                if next.len() == rest.len() {
+                    break;
+                }
+                items.push(value);
+                rest = next;
+            }
+            Ok((items, rest))
+        }
+    }
+
+    pub trait Alt<'a, T> {
+        fn choice(&self, input: &'a str) -> ParseResult<'a, T>;
+    }
+
+    macro_rules! impl_alt {
+        ($($parser:ident $index:tt),+) => {
+            impl<'a, T, $($parser),+> Alt<'a, T> for ($($parser,)+)
+            where
+                $($parser: Parser<'a, T>),+
+            {
+                // Reports the failure that got the furthest into the input.
+                fn choice(&self, input: &'a str) -> ParseResult<'a, T> {
+                    let mut furthest: Option<Failure<'a>> = None;
+                    $(
+                        match self.$index.parse(input) {
+                            Ok(result) => return Ok(result),
+                            Err(failure) => {
+                                if furthest.as_ref().map_or(true, |f| failure.rest.len() < f.rest.len()) {
+                                    furthest = Some(failure);
+                                }
+                            }
+                        }
+                    )+
+                    Err(furthest.unwrap())
+                }
+            }
+        };
+    }
+
+    impl_alt!(A 0, B 1);
+    impl_alt!(A 0, B 1, C 2);
+    impl_alt!(A 0, B 1, C 2, D 3);
+    impl_alt!(A 0, B 1, C 2, D 3, E 4);
+    impl_alt!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+    /// The first of a tuple of parsers that succeeds.
+    pub fn alt<'a, T, A>(parsers: A) -> impl Fn(&'a str) -> ParseResult<'a, T>
+    where
+        A: Alt<'a, T>,
+    {
+        move |input: &'a str| parsers.choice(input)
+    }
+
+    type BoxedParser<'a, T> = Box<dyn Parser<'a, T> + 'a>;
+
+    /// A handle to the parser being defined by `recursive`.
+    pub struct Recursive<'a, T> {
+        parser: Weak<OnceCell<BoxedParser<'a, T>>>,
+    }
+
+    impl<T> Clone for Recursive<'_, T> {
+        fn clone(&self) -> Self {
+            Self {
+                parser: self.parser.clone(),
+            }
+        }
+    }
+
+    impl<'a, T> Parser<'a, T> for Recursive<'a, T> {
+        fn parse(&self, input: &'a str) -> ParseResult<'a, T> {
+            let parser = self.parser.upgrade().expect("recursive parser used after being dropped");
+            parser.get().expect("recursive parser used while being built").parse(input)
+        }
+    }
+
+    /// Builds a self-referencing parser, `build` receives a handle to the parser it returns.
+    pub fn recursive<'a, T, P, F>(build: F) -> impl Fn(&'a str) -> ParseResult<'a, T>
+    where
+        T: 'a,
+        P: Parser<'a, T> + 'a,
+        F: FnOnce(Recursive<'a, T>) -> P,
+    {
+        let cell: Rc<OnceCell<BoxedParser<'a, T>>> = Rc::new(OnceCell::new());
+        let handle = Recursive {
+            parser: Rc::downgrade(&cell),
+        };
+        let _ = cell.set(Box::new(build(handle)));
+        move |input: &'a str| cell.get().unwrap().parse(input)
+    }
+}
 
 pub mod conversion {
 
-    use std::collections::HashMap;
+    use super::ParseError;
+    use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+
+    /// `position` is the 1-based row and token index, `None` when unknown.
+    fn try_to_value<T>(text: &str, position: Option<(usize, usize)>) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        text.parse().map_err(|error: T::Err| match position {
+            Some((row, token)) => ParseError::at_token(row, token, text, format!("{:?}", error)),
+            None => ParseError::at(0, 0, text, format!("{:?}", error)),
+        })
+    }
+
+    /// Tokens of `values` are numbered from `first_token`.
+    fn try_to_row<T>(values: &[&str], row: Option<usize>, first_token: usize) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, x)| try_to_value(x, row.map(|row| (row, first_token + i))))
+            .collect()
+    }
+
+    /// Errors carry the 1-based token index in `ParseError::token`.
+    pub fn try_to_int_vec<T>(vec: Vec<&str>) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_to_row(&vec, Some(1), 1)
+    }
 
-    pub fn to_int_vec(vec: Vec<&str>) -> Vec<i64> {
-        vec.iter().map(|&x| x.parse().unwrap()).collect()
+    pub fn to_int_vec<T>(vec: Vec<&str>) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_to_int_vec(vec).unwrap_or_else(|error| panic!("{}", error))
     }
 
-    pub fn to_int_matrix(matrix: Vec<Vec<&str>>) -> Vec<Vec<i64>> {
+    /// Errors carry the 1-based row and token indices in `ParseError::token`.
+    pub fn try_to_int_matrix<T>(matrix: Vec<Vec<&str>>) -> Result<Vec<Vec<T>>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
         matrix
             .iter()
-            .map(|row| row.iter().map(|&x| x.parse().unwrap()).collect())
+            .enumerate()
+            .map(|(i, row)| try_to_row(row, Some(i + 1), 1))
             .collect()
     }
 
-    pub fn to_int_dict<'a>(dict: HashMap<&'a str, &'a str>) -> HashMap<i64, i64> {
+    pub fn to_int_matrix<T>(matrix: Vec<Vec<&str>>) -> Vec<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_to_int_matrix(matrix).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Dictionaries are unordered, errors carry no position.
+    pub fn try_to_int_dict<'a, T>(dict: HashMap<&'a str, &'a str>) -> Result<HashMap<T, T>, ParseError>
+    where
+        T: FromStr + Eq + Hash,
+        T::Err: fmt::Debug,
+    {
         dict.iter()
-            .map(|(key, value)| {
-                let key = key.parse().unwrap();
-                let value = value.parse().unwrap();
-                (key, value)
-            })
+            .map(|(key, value)| Ok((try_to_value(key, None)?, try_to_value(value, None)?)))
             .collect()
     }
 
-    pub fn to_int_dict_vec<'a>(dict: HashMap<&'a str, Vec<&'a str>>) -> HashMap<i64, Vec<i64>> {
+    pub fn to_int_dict<'a, T>(dict: HashMap<&'a str, &'a str>) -> HashMap<T, T>
+    where
+        T: FromStr + Eq + Hash,
+        T::Err: fmt::Debug,
+    {
+        try_to_int_dict(dict).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Dictionaries are unordered, errors carry no position.
+    pub fn try_to_int_dict_vec<'a, T>(dict: HashMap<&'a str, Vec<&'a str>>) -> Result<HashMap<T, Vec<T>>, ParseError>
+    where
+        T: FromStr + Eq + Hash,
+        T::Err: fmt::Debug,
+    {
         dict.iter()
-            .map(|(key, value)| {
-                println!("{:?} {:?}", key, value);
-                let key = key.parse().unwrap();
-                let value = value.iter().map(|&x| x.parse().unwrap()).collect();
-                (key, value)
+            .map(|(key, value)| Ok((try_to_value(key, None)?, try_to_row(value, None, 1)?)))
+            .collect()
+    }
+
+    pub fn to_int_dict_vec<'a, T>(dict: HashMap<&'a str, Vec<&'a str>>) -> HashMap<T, Vec<T>>
+    where
+        T: FromStr + Eq + Hash,
+        T::Err: fmt::Debug,
+    {
+        try_to_int_dict_vec(dict).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Errors carry the 1-based tuple index in `ParseError::token`, with the key as token 1
+    /// and the values as the following tokens.
+    pub fn try_to_int_tuples_vec<'a, T>(tuples: Vec<(&'a str, Vec<&'a str>)>) -> Result<Vec<(T, Vec<T>)>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        tuples
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| Ok((try_to_value(key, Some((i + 1, 1)))?, try_to_row(value, Some(i + 1), 2)?)))
+            .collect()
+    }
+
+    pub fn to_int_tuples_vec<'a, T>(tuples: Vec<(&'a str, Vec<&'a str>)>) -> Vec<(T, Vec<T>)>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_to_int_tuples_vec(tuples).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Errors are located relative to `source`, which must contain `line`.
+    fn try_split_tokens<T>(source: &str, line: &str, split_pattern: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        line.split(split_pattern)
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|error: T::Err| ParseError::new(source, token, format!("{:?}", error)))
             })
             .collect()
     }
 
-    pub fn to_int_tuples_vec<'a>(tuples: Vec<(&'a str, Vec<&'a str>)>) -> Vec<(i64, Vec<i64>)> {
-        tuples.iter()
-            .map(|(key, value)| {
-                let key = key.parse().unwrap();
-                let value = value.iter().map(|&x| x.parse().unwrap()).collect();
-                (key, value)
+    /// Parses the non-empty parts of `line` split on `split_pattern` without collecting them first.
+    pub fn try_split_parse<T>(line: &str, split_pattern: &str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_split_tokens(line, line, split_pattern)
+    }
+
+    pub fn split_parse<T>(line: &str, split_pattern: &str) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        try_split_parse(line, split_pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn to_u8_vec(line: &str, split_pattern: &str) -> Vec<u8> {
+        split_parse(line, split_pattern)
+    }
+
+    pub fn to_usize_vec(line: &str, split_pattern: &str) -> Vec<usize> {
+        split_parse(line, split_pattern)
+    }
+
+    pub fn try_to_usize_matrix(input: &str, split_pattern: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+        input
+            .trim()
+            .lines()
+            .map(|line| try_split_tokens(input, line, split_pattern))
+            .collect()
+    }
+
+    pub fn to_usize_matrix(input: &str, split_pattern: &str) -> Vec<Vec<usize>> {
+        try_to_usize_matrix(input, split_pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// One digit per character, e.g. for height maps.
+    pub fn try_to_digit_grid(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+        input
+            .trim()
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, c)| {
+                        c.to_digit(10)
+                            .map(|digit| digit as u8)
+                            .ok_or_else(|| ParseError::new(input, &line[i..i + c.len_utf8()], "expected a digit"))
+                    })
+                    .collect()
             })
             .collect()
     }
+
+    pub fn to_digit_grid(input: &str) -> Vec<Vec<u8>> {
+        try_to_digit_grid(input).unwrap_or_else(|error| panic!("{}", error))
+    }
 }
 
 #[cfg(feature = "test_aoc_lib")]
@@ -164,7 +1324,7 @@ mod tests {
     #[test]
     fn test_conversion_to_int_vec() {
         let input = vec!["1", "2", "3"];
-        let result = super::conversion::to_int_vec(input);
+        let result = super::conversion::to_int_vec::<i64>(input);
         assert_eq!(result, vec![1, 2, 3]);
     }
 
@@ -175,7 +1335,7 @@ mod tests {
             vec!["4", "5", "6"],
             vec!["7", "8", "9"],
         ];
-        let result = super::conversion::to_int_matrix(input);
+        let result = super::conversion::to_int_matrix::<i64>(input);
         assert_eq!(result, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
     }
 
@@ -184,7 +1344,7 @@ mod tests {
         let mut input = std::collections::HashMap::new();
         input.insert("1", "2");
         input.insert("3", "4");
-        let result = super::conversion::to_int_dict(input);
+        let result = super::conversion::to_int_dict::<i64>(input);
         let mut expected = std::collections::HashMap::new();
         expected.insert(1, 2);
         expected.insert(3, 4);
@@ -196,10 +1356,352 @@ mod tests {
         let mut input = std::collections::HashMap::new();
         input.insert("1", vec!["2", "3"]);
         input.insert("4", vec!["5", "6"]);
-        let result = super::conversion::to_int_dict_vec(input);
+        let result = super::conversion::to_int_dict_vec::<i64>(input);
         let mut expected = std::collections::HashMap::new();
         expected.insert(1, vec![2, 3]);
         expected.insert(4, vec![5, 6]);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_parse_error_position() {
+        let input = "\na: 1 2\nb 3\n";
+        let result = super::try_to_dict(input, ":", " ");
+        let expected = super::ParseError::at(3, 1, "b 3", "missing separator \":\"");
+        assert_eq!(result, Err(expected));
+        assert_eq!(super::try_to_single_line(" \n "), Err(super::ParseError::at(1, 1, " \n ", "empty input")));
+    }
+
+    #[test]
+    fn test_conversion_errors() {
+        let result = super::conversion::try_to_int_matrix::<i64>(vec![vec!["1", "2"], vec!["3", "x4"]]);
+        let error = result.unwrap_err();
+        assert_eq!((error.line, error.column, error.token, error.text.as_str()), (0, 0, Some((2, 2)), "x4"));
+        assert_eq!(error.to_string(), "row 2, token 2: ParseIntError { kind: InvalidDigit } in \"x4\"");
+        let input = super::to_tuples("10: 1 2\n20: 3 -", ":", " ");
+        let error = super::conversion::try_to_int_tuples_vec::<i64>(input).unwrap_err();
+        assert_eq!((error.token, error.text.as_str()), (Some((2, 3)), "-"));
+        let input = super::to_tuples("10: 1 2\nx: 3", ":", " ");
+        let error = super::conversion::try_to_int_tuples_vec::<i64>(input).unwrap_err();
+        assert_eq!((error.token, error.text.as_str()), (Some((2, 1)), "x"));
+        let error = super::conversion::try_to_usize_matrix("1,2\n3,x", ",").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+
+        #[derive(Debug)]
+        struct Unit;
+
+        impl std::str::FromStr for Unit {
+            type Err = ();
+
+            fn from_str(text: &str) -> Result<Self, ()> {
+                (text == "u").then_some(Unit).ok_or(())
+            }
+        }
+
+        let error = super::conversion::try_split_parse::<Unit>("u u v", " ").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 5: () in \"v\"");
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(super::ints::<i64>("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+        assert_eq!(super::ints::<u32>("Button A: X+94, Y+34"), vec![94, 34]);
+        assert_eq!(super::ints::<i32>("a - b -- 7-2"), vec![7, -2]);
+        assert_eq!(super::uints::<u64>("11-22,95-115"), vec![11, 22, 95, 115]);
+        assert_eq!(super::ints::<i64>("no numbers"), Vec::<i64>::new());
+        let error = super::try_ints::<u8>("x=3, y=300").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (8, "300"));
+    }
+
+    #[test]
+    fn test_ints_per_line() {
+        let input = "190: 10 19\n3267: 81 40 27\n";
+        assert_eq!(super::ints_per_line::<u64>(input), vec![vec![190, 10, 19], vec![3267, 81, 40, 27]]);
+        assert_eq!(super::uints_per_line::<usize>("1-3 a\n2-9 c"), vec![vec![1, 3], vec![2, 9]]);
+        let error = super::try_ints_per_line::<u8>("1 2\n3 300").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "300"));
+        let error = super::try_uints_per_line::<u8>("1-2\n\n3-256").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn test_ints_n() {
+        let [x, y, dx, dy] = super::ints_n::<i64, 4>("p=0,4 v=3,-3");
+        assert_eq!((x, y, dx, dy), (0, 4, 3, -3));
+        assert_eq!(super::uints_n::<u8, 2>("3-5"), [3, 5]);
+        let error = super::try_ints_n::<i64, 3>("1 2").unwrap_err();
+        assert_eq!(error.message, "expected 3 numbers, found 2");
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "47|53\r\n97|13\r\n\r\n75,47,61\r\n97,61\r\n  \r\n";
+        assert_eq!(super::sections(input), vec!["47|53\r\n97|13", "75,47,61\r\n97,61"]);
+        let (rules, updates) = super::two_sections(input);
+        assert_eq!(super::to_column(rules), vec!["47|53", "97|13"]);
+        assert_eq!(super::to_column(updates), vec!["75,47,61", "97,61"]);
+        let [map, moves] = super::sections_n::<2>("\n##\n#.\n\n\n<>^\n");
+        assert_eq!((map, moves), ("##\n#.", "<>^"));
+    }
+
+    #[test]
+    fn test_sections_count_mismatch() {
+        let error = super::try_two_sections("a\n\nb\n\nc").unwrap_err();
+        assert_eq!(error.message, "expected 2 sections, found 3");
+        assert!(super::try_sections_n::<1>("").is_err());
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let result = super::parse_pattern!("Sensor at x={}, y={}: closest beacon is at x={}, y={}", line => i64, i64, i64, i64);
+        assert_eq!(result, Ok((2, 18, -2, 15)));
+        let result = super::parse_pattern!("#{} @ {},{}: {}x{}", "#1 @ 1,3: 4x4" => usize, u32, u32, u32, u32);
+        assert_eq!(result, Ok((1, 1, 3, 4, 4)));
+        let result = super::parse_pattern!("{} -> {}", "x AND y -> z" => String, char);
+        assert_eq!(result, Ok(("x AND y".to_string(), 'z')));
+    }
+
+    #[test]
+    fn test_parse_pattern_lines() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+        let result = super::parse_pattern!("{}-{} {}: {}", lines input => usize, usize, char, String);
+        assert_eq!(result.unwrap()[2], (2, 9, 'c', "ccccccccc".to_string()));
+
+        let input = "1-3 a: abcde\n1_3 b: cdefg";
+        let error = super::parse_pattern!("{}-{} {}: {}", lines input => usize, usize, char, String).unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 1, "expected \"-\""));
+        let error = super::parse_pattern!("{}-{} {}: {}", lines "1-3 a: b\n1-x b: c" => usize, usize, char, String).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+        let error = super::parse_pattern!("x={}, y={}", "x=1; y=2" => i64, i64).unwrap_err();
+        assert_eq!((error.column, error.text.as_str(), error.message.as_str()), (3, "1; y=2", "expected \", y=\""));
+        let error = super::parse_pattern!("x={}", "x=1" => i64, i64).unwrap_err();
+        assert_eq!(error.message, "pattern has 1 placeholders for 2 types");
+    }
+
+    #[test]
+    fn test_from_input_struct() {
+        crate::parser::from_input! {
+            #[derive(Debug, PartialEq)]
+            struct Claim = "#{} @ {},{}: {}x{}" {
+                id: usize,
+                x: u32,
+                y: u32,
+                width: u32,
+                height: u32,
+            }
+        }
+
+        crate::parser::from_input! {
+            #[derive(Debug, PartialEq)]
+            struct Equation = "{}: {}" {
+                target: u64,
+                numbers: Vec<u64>,
+            }
+        }
+
+        let claims: Vec<Claim> = super::parse_lines("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n").unwrap();
+        assert_eq!(claims[1], Claim { id: 2, x: 3, y: 1, width: 4, height: 4 });
+        let equations: Vec<Equation> = super::parse_lines("190: 10 19\n3267: 81 40 27").unwrap();
+        assert_eq!(equations[1].numbers, vec![81, 40, 27]);
+        let error = super::parse_lines::<Equation>("190: 10 19\n3267: 81 4x 27").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 10, "4x"));
+    }
+
+    #[test]
+    fn test_from_input_enum() {
+        crate::parser::from_input! {
+            #[derive(Debug, PartialEq)]
+            enum Instruction {
+                Mul(i64, i64) = "mul({},{})",
+                Cut(i64) = "cut {}",
+                Do = "do()",
+            }
+        }
+
+        let instructions: Vec<Instruction> = super::parse_lines("mul(2,4)\ndo()\ncut -3").unwrap();
+        assert_eq!(instructions, vec![Instruction::Mul(2, 4), Instruction::Do, Instruction::Cut(-3)]);
+        let error = super::parse_lines::<Instruction>("do()\nmul(2,x)").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 7, "x"));
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let maps: Vec<super::super::map::Map> = super::parse_sections("#.\n.#\n\n##\n").unwrap();
+        assert_eq!(maps[1].size(), (2, 1));
+        let numbers: Vec<Vec<i32>> = super::parse_sections("1 2\n3\n\n4,5").unwrap();
+        assert_eq!(numbers, vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_combinator_packets() {
+        use super::combinator::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Packet {
+            Integer(u32),
+            List(Vec<Packet>),
+        }
+
+        fn packet(input: &str) -> ParseResult<'_, Packet> {
+            alt((
+                map(number(), Packet::Integer),
+                map(delimited(tag("["), separated_list(packet, tag(",")), tag("]")), Packet::List),
+            ))(input)
+        }
+
+        let result = parse_all("[[1],[2,[3]],[]]", packet);
+        let expected = Packet::List(vec![
+            Packet::List(vec![Packet::Integer(1)]),
+            Packet::List(vec![Packet::Integer(2), Packet::List(vec![Packet::Integer(3)])]),
+            Packet::List(vec![]),
+        ]);
+        assert_eq!(result, Ok(expected));
+
+        let error = parse_all("[1,[2,x]]", packet).unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (1, 7, "expected number"));
+    }
+
+    #[test]
+    fn test_combinator_recursive() {
+        use super::combinator::*;
+
+        #[derive(Debug, PartialEq)]
+        enum Snailfish {
+            Regular(u8),
+            Pair(Box<Snailfish>, Box<Snailfish>),
+        }
+
+        let snailfish = recursive(|snailfish| {
+            alt((
+                map(number(), Snailfish::Regular),
+                map(
+                    delimited(tag("["), pair(terminated(snailfish.clone(), tag(",")), snailfish), tag("]")),
+                    |(left, right)| Snailfish::Pair(Box::new(left), Box::new(right)),
+                ),
+            ))
+        });
+        let (value, rest) = snailfish("[[1,2],3] tail").unwrap();
+        let expected = Snailfish::Pair(
+            Box::new(Snailfish::Pair(Box::new(Snailfish::Regular(1)), Box::new(Snailfish::Regular(2)))),
+            Box::new(Snailfish::Regular(3)),
+        );
+        assert_eq!((value, rest), (expected, " tail"));
+    }
+
+    #[test]
+    fn test_combinator_workflow() {
+        use super::combinator::*;
+
+        let name = || take_while1(|c: char| c.is_ascii_alphabetic());
+        let condition = pair(pair(take_while1(|c| "xmas".contains(c)), alt((tag("<"), tag(">")))), number::<u32>());
+        let rule = pair(opt(terminated(condition, tag(":"))), name());
+        let workflow = pair(name(), delimited(tag("{"), separated_list(rule, tag(",")), tag("}")));
+        let (label, rules) = parse_all("px{a<2006:qkq,m>2090:A,rfg}", workflow).unwrap();
+        assert_eq!(label, "px");
+        assert_eq!(rules[0], (Some((("a", "<"), 2006)), "qkq"));
+        assert_eq!(rules[2], (None, "rfg"));
+
+        let lines = "ab\ncd:x";
+        let error = parse_all(lines, separated_list(name(), tag("\n"))).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, ":x"));
+    }
+
+    #[test]
+    fn test_conversion_generic() {
+        let result: Vec<u64> = super::conversion::to_int_vec(vec!["1", "2", "3"]);
+        assert_eq!(result, vec![1, 2, 3]);
+        let result = super::conversion::to_int_tuples_vec::<u64>(super::to_tuples("190: 10 19", ":", " "));
+        assert_eq!(result, vec![(190, vec![10, 19])]);
+        let result: Vec<Vec<u8>> = super::conversion::to_int_matrix(vec![vec!["1", "2"]]);
+        assert_eq!(result, vec![vec![1, 2]]);
+        assert!(super::conversion::try_to_int_vec::<u8>(vec!["256"]).is_err());
+    }
+
+    #[test]
+    fn test_conversion_without_tokens() {
+        assert_eq!(super::conversion::to_usize_vec("75,47,61", ","), vec![75, 47, 61]);
+        assert_eq!(super::conversion::to_u8_vec(" 1  2 3 ", " "), vec![1, 2, 3]);
+        assert_eq!(super::conversion::to_usize_matrix("1 2\n3 4\n", " "), vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(super::conversion::to_digit_grid("0123\n4567\n"), vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+        let error = super::conversion::try_to_digit_grid("012\n3.5").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "."));
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(super::ranges("11-22,95-115,\n998-1012"), vec![11..=22, 95..=115, 998..=1012]);
+        assert_eq!(super::ranges("2-4,6-8\n-3--1"), vec![2..=4, 6..=8, -3..=-1]);
+        assert_eq!(super::length_ranges("seeds: 79 14 55 13"), vec![79..=92, 55..=67]);
+        let error = super::try_ranges("3-5\n10-x").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 1, "10-x"));
+        assert!(super::try_length_ranges("1 2 3").is_err());
+    }
+
+    #[test]
+    fn test_byte_parser() {
+        let mut parser = super::ByteParser::new("190: 10 19\r\n3267: 81 -40 +27\n");
+        assert_eq!(parser.u64(), Some(190));
+        parser.expect(b":");
+        parser.skip_ws();
+        assert_eq!(parser.u64(), Some(10));
+        parser.skip_ws();
+        assert_eq!(parser.u64(), Some(19));
+        parser.skip_ws();
+        assert_eq!(parser.until(b':'), b"3267");
+        parser.skip_ws();
+        assert_eq!(parser.i64(), Some(81));
+        parser.skip_ws();
+        assert_eq!(parser.i64(), Some(-40));
+        parser.skip_ws();
+        assert_eq!(parser.i64(), Some(27));
+        assert_eq!(parser.u64(), None);
+        parser.skip_ws();
+        assert!(parser.is_empty());
+    }
+
+    #[test]
+    fn test_byte_parser_lines() {
+        let mut parser = super::ByteParser::new("a -> b\r\nc -> d\n");
+        let mut edges = Vec::new();
+        while let Some(line) = parser.line() {
+            let mut line = super::ByteParser::from_bytes(line);
+            let start = line.until(b' ');
+            line.expect(b"-> ");
+            edges.push((start, line.remaining()));
+        }
+        assert_eq!(edges, vec![(&b"a"[..], &b"b"[..]), (b"c", b"d")]);
+        let mut parser = super::ByteParser::new("first\n\nlast");
+        assert_eq!(parser.line(), Some(&b"first"[..]));
+        assert_eq!(parser.line(), Some(&b""[..]));
+        assert_eq!(parser.line(), Some(&b"last"[..]));
+        assert_eq!(parser.line(), None);
+        let mut parser = super::ByteParser::new("x=-3, y=abc");
+        parser.skip_to_number();
+        assert_eq!(parser.i64(), Some(-3));
+        assert!(!parser.eat(b"y"));
+        assert!(parser.eat(b", y="));
+        assert_eq!(parser.i64(), None);
+        assert_eq!(parser.position(), 8);
+    }
+
+    #[test]
+    fn test_byte_parser_edge_cases() {
+        let mut parser = super::ByteParser::new("3-x 5 -");
+        let mut numbers = Vec::new();
+        while !parser.is_empty() {
+            parser.skip_to_number();
+            numbers.extend(parser.i64());
+        }
+        assert_eq!(numbers, vec![3, 5]);
+
+        let mut parser = super::ByteParser::new("-9223372036854775808 9223372036854775808 99999999999999999999 1");
+        assert_eq!(parser.i64(), Some(i64::MIN));
+        parser.skip_ws();
+        assert_eq!(parser.i64(), None);
+        parser.skip_ws();
+        assert_eq!(parser.u64(), None);
+        parser.skip_ws();
+        assert_eq!(parser.u64(), Some(1));
+    }
 }
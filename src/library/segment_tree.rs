@@ -0,0 +1,334 @@
+#![allow(dead_code)]
+
+use std::ops::{Bound, RangeBounds};
+
+/// Associative operation with an identity element, the values stored in a `SegmentTree`.
+pub trait Monoid {
+    type Value: Clone;
+
+    fn identity() -> Self::Value;
+
+    fn combine(left: &Self::Value, right: &Self::Value) -> Self::Value;
+}
+
+/// Monoid with updates that can be applied to a whole range at once.
+/// Use `type Update = ()` when only point updates are needed.
+pub trait LazyMonoid: Monoid {
+    type Update: Clone;
+
+    fn no_update() -> Self::Update;
+
+    /// Applies `update` to the combined value of `len` elements.
+    fn apply(update: &Self::Update, value: &Self::Value, len: usize) -> Self::Value;
+
+    /// Update equivalent to applying `older` then `newer`.
+    fn compose(newer: &Self::Update, older: &Self::Update) -> Self::Update;
+}
+
+/// Range sums of `i64` with range additions.
+pub struct RangeAddSum;
+
+impl Monoid for RangeAddSum {
+    type Value = i64;
+
+    fn identity() -> i64 {
+        0
+    }
+
+    fn combine(left: &i64, right: &i64) -> i64 {
+        left + right
+    }
+}
+
+impl LazyMonoid for RangeAddSum {
+    type Update = i64;
+
+    fn no_update() -> i64 {
+        0
+    }
+
+    fn apply(update: &i64, value: &i64, len: usize) -> i64 {
+        value + update * len as i64
+    }
+
+    fn compose(newer: &i64, older: &i64) -> i64 {
+        newer + older
+    }
+}
+
+/// Range minimums of `i64` with range additions.
+pub struct RangeAddMin;
+
+impl Monoid for RangeAddMin {
+    type Value = i64;
+
+    fn identity() -> i64 {
+        i64::MAX
+    }
+
+    fn combine(left: &i64, right: &i64) -> i64 {
+        *left.min(right)
+    }
+}
+
+impl LazyMonoid for RangeAddMin {
+    type Update = i64;
+
+    fn no_update() -> i64 {
+        0
+    }
+
+    fn apply(update: &i64, value: &i64, _len: usize) -> i64 {
+        value + update
+    }
+
+    fn compose(newer: &i64, older: &i64) -> i64 {
+        newer + older
+    }
+}
+
+/// Range maximums of `i64` with range additions.
+pub struct RangeAddMax;
+
+impl Monoid for RangeAddMax {
+    type Value = i64;
+
+    fn identity() -> i64 {
+        i64::MIN
+    }
+
+    fn combine(left: &i64, right: &i64) -> i64 {
+        *left.max(right)
+    }
+}
+
+impl LazyMonoid for RangeAddMax {
+    type Update = i64;
+
+    fn no_update() -> i64 {
+        0
+    }
+
+    fn apply(update: &i64, value: &i64, _len: usize) -> i64 {
+        value + update
+    }
+
+    fn compose(newer: &i64, older: &i64) -> i64 {
+        newer + older
+    }
+}
+
+/// Lazy segment tree with range queries and range updates in logarithmic time.
+pub struct SegmentTree<M: LazyMonoid> {
+    len: usize,
+    size: usize,
+    values: Vec<M::Value>,
+    pending: Vec<M::Update>,
+}
+
+impl<M: LazyMonoid> SegmentTree<M> {
+    /// Tree of `len` identity values.
+    pub fn new(len: usize) -> Self {
+        Self::from(vec![M::identity(); len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Combined value of the elements in `range`.
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> M::Value {
+        let (start, end) = self.bounds(range);
+        self.query_node(1, 0, self.size, start, end)
+    }
+
+    pub fn get(&mut self, index: usize) -> M::Value {
+        self.query(index..=index)
+    }
+
+    /// Replaces the element at `index`.
+    pub fn set(&mut self, index: usize, value: M::Value) {
+        assert!(index < self.len, "index {index} is out of the tree of length {}", self.len);
+        self.set_node(1, 0, self.size, index, value);
+    }
+
+    /// Applies `update` to every element in `range`.
+    pub fn update<R: RangeBounds<usize>>(&mut self, range: R, update: M::Update) {
+        let (start, end) = self.bounds(range);
+        self.update_node(1, 0, self.size, start, end, &update);
+    }
+
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        (start, end.min(self.len))
+    }
+
+    fn apply_node(&mut self, node: usize, left: usize, right: usize, update: &M::Update) {
+        // Padding nodes past the end keep the identity
+        if left >= self.len {
+            return;
+        }
+        self.values[node] = M::apply(update, &self.values[node], right.min(self.len) - left);
+        if node < self.size {
+            self.pending[node] = M::compose(update, &self.pending[node]);
+        }
+    }
+
+    fn push_down(&mut self, node: usize, left: usize, right: usize) {
+        let update = std::mem::replace(&mut self.pending[node], M::no_update());
+        let middle = (left + right) / 2;
+        self.apply_node(2 * node, left, middle, &update);
+        self.apply_node(2 * node + 1, middle, right, &update);
+    }
+
+    fn pull_up(&mut self, node: usize) {
+        self.values[node] = M::combine(&self.values[2 * node], &self.values[2 * node + 1]);
+    }
+
+    fn query_node(&mut self, node: usize, left: usize, right: usize, start: usize, end: usize) -> M::Value {
+        if end <= left || right <= start {
+            return M::identity();
+        }
+        if start <= left && right <= end {
+            return self.values[node].clone();
+        }
+        self.push_down(node, left, right);
+        let middle = (left + right) / 2;
+        let left_value = self.query_node(2 * node, left, middle, start, end);
+        let right_value = self.query_node(2 * node + 1, middle, right, start, end);
+        M::combine(&left_value, &right_value)
+    }
+
+    fn set_node(&mut self, node: usize, left: usize, right: usize, index: usize, value: M::Value) {
+        if right - left == 1 {
+            self.values[node] = value;
+            return;
+        }
+        self.push_down(node, left, right);
+        let middle = (left + right) / 2;
+        if index < middle {
+            self.set_node(2 * node, left, middle, index, value);
+        } else {
+            self.set_node(2 * node + 1, middle, right, index, value);
+        }
+        self.pull_up(node);
+    }
+
+    fn update_node(&mut self, node: usize, left: usize, right: usize, start: usize, end: usize, update: &M::Update) {
+        if end <= left || right <= start {
+            return;
+        }
+        if start <= left && right <= end {
+            self.apply_node(node, left, right, update);
+            return;
+        }
+        self.push_down(node, left, right);
+        let middle = (left + right) / 2;
+        self.update_node(2 * node, left, middle, start, end, update);
+        self.update_node(2 * node + 1, middle, right, start, end, update);
+        self.pull_up(node);
+    }
+}
+
+impl<M: LazyMonoid> From<Vec<M::Value>> for SegmentTree<M> {
+    fn from(values: Vec<M::Value>) -> Self {
+        let len = values.len();
+        let size = len.next_power_of_two();
+        let mut tree = Self {
+            len,
+            size,
+            values: vec![M::identity(); 2 * size],
+            pending: vec![M::no_update(); size],
+        };
+        for (i, value) in values.into_iter().enumerate() {
+            tree.values[size + i] = value;
+        }
+        for node in (1..size).rev() {
+            tree.pull_up(node);
+        }
+        tree
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_segment_tree_sum() {
+        let mut values = vec![5i64, -2, 7, 0, 3, 8, -4];
+        let mut tree = super::SegmentTree::<super::RangeAddSum>::from(values.clone());
+        assert_eq!(tree.query(..), 17);
+        tree.update(1..5, 10);
+        values[1..5].iter_mut().for_each(|value| *value += 10);
+        tree.set(6, 1);
+        values[6] = 1;
+        for start in 0..values.len() {
+            for end in start..=values.len() {
+                assert_eq!(tree.query(start..end), values[start..end].iter().sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment_tree_min_max() {
+        let values = vec![4i64, 9, 1, 7, 3];
+        let mut min = super::SegmentTree::<super::RangeAddMin>::from(values.clone());
+        let mut max = super::SegmentTree::<super::RangeAddMax>::from(values);
+        assert_eq!(min.query(..), 1);
+        assert_eq!(max.query(2..), 7);
+        min.update(2..=2, 5);
+        max.update(..2, -6);
+        assert_eq!(min.query(..), 3);
+        assert_eq!(max.query(..), 7);
+        assert_eq!(min.get(2), 6);
+        assert_eq!(max.query(..2), 3);
+    }
+
+    #[test]
+    fn test_segment_tree_user_monoid() {
+        // Not commutative, checks that elements are combined in order
+        struct Concat;
+
+        impl super::Monoid for Concat {
+            type Value = String;
+
+            fn identity() -> String {
+                String::new()
+            }
+
+            fn combine(left: &String, right: &String) -> String {
+                format!("{left}{right}")
+            }
+        }
+
+        impl super::LazyMonoid for Concat {
+            type Update = ();
+
+            fn no_update() {}
+
+            fn apply(_: &(), value: &String, _: usize) -> String {
+                value.clone()
+            }
+
+            fn compose(_: &(), _: &()) {}
+        }
+
+        let mut tree = super::SegmentTree::<Concat>::from("segment".chars().map(String::from).collect::<Vec<_>>());
+        assert_eq!(tree.query(1..5), "egme");
+        tree.set(0, "S".to_string());
+        assert_eq!(tree.query(..), "Segment");
+    }
+}
@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use super::linear::{self, Rational, Solution};
+
+/// Differences between consecutive values.
+pub fn differences(values: &[i64]) -> Vec<i64> {
+    values.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+/// Rows of repeated differences, starting with `values` and ending with a constant row.
+pub fn difference_table(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut table = vec![values.to_vec()];
+    while let Some(last) = table.last() {
+        if last.len() <= 1 || last.iter().all(|&value| value == last[0]) {
+            break;
+        }
+        let next = differences(last);
+        table.push(next);
+    }
+    table
+}
+
+/// Next value of the sequence, assuming its differences end up constant.
+pub fn extrapolate_forward(values: &[i64]) -> i64 {
+    difference_table(values)
+        .iter()
+        .filter_map(|row| row.last())
+        .sum()
+}
+
+/// Value before the first one of the sequence, assuming its differences end up constant.
+pub fn extrapolate_backward(values: &[i64]) -> i64 {
+    difference_table(values)
+        .iter()
+        .rev()
+        .filter_map(|row| row.first())
+        .fold(0, |below, &first| first - below)
+}
+
+/// Value at `x` of the lowest degree polynomial going through `points`, computed exactly.
+/// `None` if two points share the same `x`.
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Option<Rational> {
+    let mut total = Rational::ZERO;
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = Rational::from(yi);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                if xi == xj {
+                    return None;
+                }
+                term = term * Rational::new(x - xj, xi - xj);
+            }
+        }
+        total = total + term;
+    }
+    Some(total)
+}
+
+/// Polynomial with exact coefficients, lowest degree first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        Self { coefficients }
+    }
+
+    /// Fits the polynomial of degree `points.len() - 1` through `points`,
+    /// `None` if two points share the same `x` with different values.
+    pub fn fit(points: &[(i128, i128)]) -> Option<Self> {
+        let matrix = points
+            .iter()
+            .map(|&(x, _)| {
+                let mut power = 1;
+                (0..points.len())
+                    .map(|i| {
+                        if i > 0 {
+                            power *= x;
+                        }
+                        power
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<i128>>>();
+        let rhs = points.iter().map(|&(_, y)| y).collect::<Vec<i128>>();
+        match linear::solve_integers(&matrix, &rhs) {
+            Solution::Unique(coefficients) | Solution::Infinite(coefficients) => Some(Self::new(coefficients)),
+            Solution::NoSolution => None,
+        }
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    pub fn evaluate(&self, x: i128) -> Rational {
+        let x = Rational::from(x);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::ZERO, |total, &coefficient| total * x + coefficient)
+    }
+}
+
+#[cfg(feature = "test_aoc_lib")]
+mod tests {
+
+    #[test]
+    fn test_extrapolate() {
+        let histories = crate::parser::ints_per_line::<i64>("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45");
+        let forward = histories.iter().map(|h| super::extrapolate_forward(h)).collect::<Vec<i64>>();
+        assert_eq!(forward, vec![18, 28, 68]);
+        let backward = histories.iter().map(|h| super::extrapolate_backward(h)).collect::<Vec<i64>>();
+        assert_eq!(backward, vec![-3, 0, 5]);
+        assert_eq!(super::difference_table(&histories[1]).len(), 3);
+    }
+
+    #[test]
+    fn test_polynomial_fit() {
+        // f(x) = (3x^2 + x) / 2 + 7
+        let f = |x: i128| (3 * x * x + x) / 2 + 7;
+        let points = [65, 196, 327].map(|x| (x, f(x)));
+        let polynomial = super::Polynomial::fit(&points).unwrap();
+        let expected = [super::Rational::from(7i64), super::Rational::new(1, 2), super::Rational::new(3, 2)];
+        assert_eq!(polynomial.coefficients(), expected);
+        let x = 26501365;
+        assert_eq!(polynomial.evaluate(x).to_integer(), Some(f(x)));
+        assert_eq!(super::lagrange(&points, x).and_then(|value| value.to_integer()), Some(f(x)));
+        assert_eq!(super::lagrange(&[(1, 2), (1, 2)], 5), None);
+        assert_eq!(super::Polynomial::fit(&[(1, 2), (1, 3)]), None);
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+/// A problem found in a puzzle input before it reaches a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputIssue {
+    /// The input contains nothing but whitespace.
+    Empty,
+    /// Lines end with `\r\n` instead of `\n`.
+    CrlfLineEndings,
+    /// The input should end with exactly one newline, as downloaded inputs do.
+    TrailingNewlines { found: usize },
+    /// A grid line does not have the width of the first line.
+    NonRectangular { line: usize, width: usize, expected: usize },
+    /// A control or non-ASCII character, or a character outside of the allowed grid set.
+    UnexpectedCharacter { line: usize, column: usize, character: char },
+}
+
+impl Display for InputIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "input is empty"),
+            Self::CrlfLineEndings => write!(f, "input uses CRLF line endings"),
+            Self::TrailingNewlines { found } => {
+                write!(f, "input ends with {found} newlines instead of 1")
+            }
+            Self::NonRectangular {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {line} is {width} characters wide, expected {expected}"
+            ),
+            Self::UnexpectedCharacter {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "unexpected character {character:?} at line {line}, column {column}"
+            ),
+        }
+    }
+}
+
+/// Diagnostics about the shape of a puzzle input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputReport {
+    pub issues: Vec<InputIssue>,
+    /// Number of non-blank lines.
+    pub lines: usize,
+}
+
+impl InputReport {
+    /// Checks for an empty input, CRLF line endings, the trailing newline and control characters.
+    pub fn new(input: &str) -> Self {
+        let mut issues = Vec::new();
+        if input.trim().is_empty() {
+            issues.push(InputIssue::Empty);
+            return Self { issues, lines: 0 };
+        }
+        if input.contains("\r\n") {
+            issues.push(InputIssue::CrlfLineEndings);
+        }
+        let trailing = &input[input.trim_end_matches(['\n', '\r']).len()..];
+        let found = trailing.matches('\n').count();
+        if found != 1 {
+            issues.push(InputIssue::TrailingNewlines { found });
+        }
+        for (y, line) in input.lines().enumerate() {
+            for (x, character) in line.chars().enumerate() {
+                if !(character.is_ascii_graphic() || character == ' ' || character == '\t') {
+                    issues.push(InputIssue::UnexpectedCharacter {
+                        line: y + 1,
+                        column: x + 1,
+                        character,
+                    });
+                }
+            }
+        }
+        let lines = input.lines().filter(|line| !line.trim().is_empty()).count();
+        Self { issues, lines }
+    }
+
+    /// Same checks as [`InputReport::new`], and also requires every line to be as wide as
+    /// the first one and to only contain characters from `allowed`.
+    pub fn grid(input: &str, allowed: &str) -> Self {
+        let mut report = Self::new(input);
+        report.check_grid(input, |character| allowed.contains(character));
+        report
+    }
+
+    /// Runs the grid checks of [`InputReport::grid`] when the input looks like a grid,
+    /// allowing any printable ASCII character, and only the checks of [`InputReport::new`] otherwise.
+    ///
+    /// An input looks like a grid when it is a single block of several lines without whitespace.
+    /// Inputs made only of digits are left alone, as they are more often number lists than digit grids.
+    pub fn detect(input: &str) -> Self {
+        let mut report = Self::new(input);
+        let rows = input.trim().lines().collect::<Vec<&str>>();
+        let is_grid = rows.len() > 1
+            && rows.iter().all(|row| !row.is_empty() && !row.contains(char::is_whitespace))
+            && !rows.iter().all(|row| row.chars().all(|c| c.is_ascii_digit() || c == '-'));
+        if is_grid {
+            report.check_grid(input, |character| character.is_ascii_graphic());
+        }
+        report
+    }
+
+    /// Blank lines are skipped but still counted, so that line numbers match [`InputReport::new`].
+    fn check_grid<F>(&mut self, input: &str, allowed: F)
+    where
+        F: Fn(char) -> bool,
+    {
+        let reported = self
+            .issues
+            .iter()
+            .filter_map(|issue| match issue {
+                InputIssue::UnexpectedCharacter { line, column, .. } => Some((*line, *column)),
+                _ => None,
+            })
+            .collect::<HashSet<(usize, usize)>>();
+        let mut expected = None;
+        for (y, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let width = line.chars().count();
+            let expected = *expected.get_or_insert(width);
+            if width != expected {
+                self.issues.push(InputIssue::NonRectangular {
+                    line: y + 1,
+                    width,
+                    expected,
+                });
+            }
+            for (x, character) in line.chars().enumerate() {
+                if !allowed(character) && !reported.contains(&(y + 1, x + 1)) {
+                    self.issues.push(InputIssue::UnexpectedCharacter {
+                        line: y + 1,
+                        column: x + 1,
+                        character,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Whether no issue was found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for InputReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "input looks fine ({} lines)", self.lines);
+        }
+        let issues = self
+            .issues
+            .iter()
+            .map(|issue| format!("  - {issue}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        write!(f, "input has {} issue(s):\n{issues}", self.issues.len())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{InputIssue, InputReport};
+
+    #[test]
+    fn clean_input() {
+        let report = InputReport::new("1 2\n3 4\n");
+        assert!(report.is_clean());
+        assert_eq!(report.lines, 2);
+    }
+
+    #[test]
+    fn empty_input() {
+        let report = InputReport::new(" \n\n");
+        assert_eq!(report.issues, vec![InputIssue::Empty]);
+    }
+
+    #[test]
+    fn line_endings() {
+        let report = InputReport::new("a\r\nb\r\n");
+        assert_eq!(report.issues, vec![InputIssue::CrlfLineEndings]);
+        let report = InputReport::new("a\nb");
+        assert_eq!(report.issues, vec![InputIssue::TrailingNewlines { found: 0 }]);
+        let report = InputReport::new("a\nb\n\n");
+        assert_eq!(report.issues, vec![InputIssue::TrailingNewlines { found: 2 }]);
+    }
+
+    #[test]
+    fn grid_input() {
+        let report = InputReport::grid("#.#\n.#\n#x#\n", "#.");
+        assert_eq!(
+            report.issues,
+            vec![
+                InputIssue::NonRectangular {
+                    line: 2,
+                    width: 2,
+                    expected: 3
+                },
+                InputIssue::UnexpectedCharacter {
+                    line: 3,
+                    column: 2,
+                    character: 'x'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_line_numbers() {
+        let report = InputReport::grid("\n#\u{1}#\n##\n", "#");
+        assert_eq!(
+            report.issues,
+            vec![
+                InputIssue::UnexpectedCharacter {
+                    line: 2,
+                    column: 2,
+                    character: '\u{1}'
+                },
+                InputIssue::NonRectangular {
+                    line: 3,
+                    width: 2,
+                    expected: 3
+                },
+            ]
+        );
+        let report = InputReport::grid("  ##\n####\n", "#");
+        assert_eq!(
+            report.issues,
+            vec![
+                InputIssue::UnexpectedCharacter {
+                    line: 1,
+                    column: 1,
+                    character: ' '
+                },
+                InputIssue::UnexpectedCharacter {
+                    line: 1,
+                    column: 2,
+                    character: ' '
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn detect_grid() {
+        let report = InputReport::detect("#.#\r\n.#\r\n");
+        assert_eq!(
+            report.issues,
+            vec![
+                InputIssue::CrlfLineEndings,
+                InputIssue::NonRectangular {
+                    line: 2,
+                    width: 2,
+                    expected: 3
+                },
+            ]
+        );
+        assert!(InputReport::detect("3   4\n4   3\n10   3\n").is_clean());
+        assert!(InputReport::detect("1721\n979\n366\n").is_clean());
+        assert!(InputReport::detect("2333133121414131402\n").is_clean());
+        assert!(InputReport::detect("#####\n#.O@#\n#####\n\n<^^>>v\n").is_clean());
+    }
+}
@@ -5,9 +5,11 @@ pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use input_report::*;
 pub use year::*;
 
 mod day;
+mod input_report;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
@@ -26,6 +28,14 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string, along with diagnostics about its contents.
+#[must_use]
+pub fn read_file_report(folder: &str, day: Day) -> (String, InputReport) {
+    let input = read_file(folder, day);
+    let report = InputReport::detect(&input);
+    (input, report)
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -63,7 +73,10 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            let (input, report) = $crate::template::read_file_report("inputs", DAY);
+            if !report.is_clean() {
+                eprintln!("{report}");
+            }
             $( run_part($func, &input, DAY, $part); )*
         }
     };